@@ -1,4 +1,4 @@
-use day_01::part1;
+use aoc_2019_day_01::part1;
 use std::fs;
 
 fn main() {
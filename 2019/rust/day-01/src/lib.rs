@@ -1,3 +1,5 @@
+use aoc_common::{Answer, Result, Solution};
+
 pub fn part1(source: &str) -> String {
     source
         .lines()
@@ -7,7 +9,6 @@ pub fn part1(source: &str) -> String {
         .to_string()
 }
 
-
 pub fn part2(source: &str) -> String {
     source
         .lines()
@@ -17,6 +18,18 @@ pub fn part2(source: &str) -> String {
         .to_string()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 fn count_fuel(mass: u64) -> u64 {
     ((mass as f32 / 3.0).floor() as u64).saturating_sub(2)
 }
@@ -37,18 +50,5 @@ fn count_fuel_recursive(mass: u64) -> u64 {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_fuel_counter() {
-        assert_eq!(count_fuel(12), 2);
-        assert_eq!(count_fuel(14), 2);
-        assert_eq!(count_fuel(1969), 654);
-        assert_eq!(count_fuel(100756), 33583);
-    }
-
-    #[test]
-    fn test_recursive_counter() {
-        assert_eq!(count_fuel_recursive(14), 2);
-        assert_eq!(count_fuel_recursive(1969), 966);
-        assert_eq!(count_fuel_recursive(100756), 50346);
-    }
+    aoc_common::examples!(Solver);
 }
@@ -1,7 +1,10 @@
-use day_02::part1;
-use std::fs;
+use aoc_2019_day_02::part1;
+use aoc_common::{Params, Result};
+use std::{fs, path::Path};
 
-fn main() {
+fn main() -> Result<()> {
     let file = fs::read_to_string("./real_input.txt").unwrap();
-    println!("{}", part1(&file));
+    let params = Params::load(Path::new("."), Path::new("./real_input.txt"))?;
+    println!("{}", part1(&file, &params)?);
+    Ok(())
 }
@@ -1,7 +1,9 @@
-use day_02::part2;
+use aoc_2019_day_02::part2;
+use aoc_common::Result;
 use std::fs;
 
-fn main() {
+fn main() -> Result<()> {
     let file = fs::read_to_string("./real_input.txt").unwrap();
-    println!("{}", part2(&file));
+    println!("{}", part2(&file)?);
+    Ok(())
 }
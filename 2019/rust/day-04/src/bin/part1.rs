@@ -1,7 +1,7 @@
-use day_04::part1;
+use aoc_2019_day_04::part1;
 use std::fs;
 
 fn main() {
     let file = fs::read_to_string("./real_input.txt").unwrap();
-    println!("{}", part1(&file));
+    println!("{}", part1(&file).unwrap());
 }
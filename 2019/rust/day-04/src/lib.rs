@@ -1,29 +1,55 @@
-pub fn part1(_source: &str) -> String {
+use aoc_common::{eyre::eyre, Answer, Result, Solution};
+use std::ops::RangeInclusive;
+
+pub fn part1(source: &str) -> Result<String> {
     let mut c = 0u32;
 
-    numz(|n| {
+    numz(parse_range(source)?, |n| {
         if verify(n) {
             c += 1;
         }
     });
 
-    c.to_string()
+    Ok(c.to_string())
 }
 
-pub fn part2(_source: &str) -> String {
+pub fn part2(source: &str) -> Result<String> {
     let mut c = 0u32;
 
-    numz(|n| {
+    numz(parse_range(source)?, |n| {
         if verify2(n) {
             c += 1;
         }
     });
 
-    c.to_string()
+    Ok(c.to_string())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+/// The input is the range of the passwords, like `111111-999999`.
+fn parse_range(source: &str) -> Result<RangeInclusive<u32>> {
+    let (low, high) = source
+        .trim()
+        .split_once('-')
+        .ok_or_else(|| eyre!("Expected a range like 111111-999999, got {:?}!", source.trim()))?;
+
+    Ok(low.parse()?..=high.parse()?)
 }
 
-fn numz(mut verifier: impl FnMut(u32)) {
-    'main: for v1 in 4..=8 {
+/// Calls `verifier` with every number of `range` whose digits never decrease.
+fn numz(range: RangeInclusive<u32>, mut verifier: impl FnMut(u32)) {
+    'main: for v1 in range.start() / 100_000..=9 {
         for v2 in v1..=9 {
             for v3 in v2..=9 {
                 for v4 in v3..=9 {
@@ -37,10 +63,10 @@ fn numz(mut verifier: impl FnMut(u32)) {
                                 + v5 * 10
                                 + v6;
 
-                            if num < 402328 {
+                            if num < *range.start() {
                                 continue;
                             }
-                            if num >= 864247 {
+                            if num > *range.end() {
                                 break 'main;
                             }
                             verifier(num);
@@ -84,6 +110,8 @@ fn verify2(number: u32) -> bool {
 mod tests {
     use super::*;
 
+    aoc_common::examples!(Solver);
+
     #[test]
     fn test_part1() {
         assert!(verify(112));
@@ -100,6 +128,11 @@ mod tests {
         assert_eq!(a.pow(5), 100000);
     }
 
+    #[test]
+    fn test_range() {
+        assert!(parse_range("111110").is_err());
+    }
+
     #[test]
     fn test_part2() {
         assert!(verify2(112));
@@ -1,7 +1,11 @@
-use day_05::part2;
+use aoc_2019_day_05::part2;
+use aoc_common::Result;
 use std::fs;
 
-fn main() {
+fn main() -> Result<()> {
+    aoc_common::log::init(0);
+
     let file = fs::read_to_string("./real_input.txt").unwrap();
-    println!("{}", part2(&file));
+    println!("{}", part2(&file)?);
+    Ok(())
 }
@@ -1,14 +1,58 @@
-mod executor;
-use executor::*;
+use aoc_common::{
+    eyre::eyre,
+    rand::{rngs::StdRng, SeedableRng},
+    Answer, Result, Solution,
+};
+use aoc_intcode::{generate, Executor, Memory};
+use std::hint::black_box;
 
-pub fn part1(source: &str) -> String {
-    let mut e = Executor::new(source);
-    e.execute();
-    e.output.iter().map(i32::to_string).collect::<String>()
+pub fn part1(source: &str) -> Result<String> {
+    let mut e = Executor::with_input(source, "1")?;
+    e.execute()?;
+
+    // every test before the diagnostic code outputs how far off it was
+    let (code, tests) = e
+        .output
+        .split_last()
+        .ok_or_else(|| eyre!("The program has not output anything!"))?;
+    if let Some(i) = tests.iter().position(|&t| t != 0) {
+        return Err(eyre!("The test {i} failed, it was off by {}!", tests[i]));
+    }
+
+    Ok(code.to_string())
+}
+
+pub fn part2(source: &str) -> Result<String> {
+    let mut e = Executor::with_input(source, "5")?;
+    e.execute()?;
+    Ok(e.output.iter().map(i64::to_string).collect::<String>())
 }
 
-pub fn part2(source: &str) -> String {
-    let mut e = Executor::with_input(source, "5");
-    e.execute();
-    e.output.iter().map(i32::to_string).collect::<String>()
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(Memory::from(input).map(|m| {
+            black_box(m);
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::examples!(Solver);
 }
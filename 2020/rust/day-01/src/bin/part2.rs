@@ -1,4 +1,4 @@
-use day_01::part2;
+use aoc_2020_day_01::part2;
 use std::fs;
 
 fn main() {
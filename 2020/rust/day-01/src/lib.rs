@@ -1,3 +1,4 @@
+use aoc_common::{Answer, Result, Solution};
 use itertools::Itertools;
 
 pub fn part1(source: &str) -> String {
@@ -14,7 +15,6 @@ pub fn part1(source: &str) -> String {
     (r.0 * r.1).to_string()
 }
 
-
 pub fn part2(source: &str) -> String {
     let r = source
         .lines()
@@ -29,21 +29,21 @@ pub fn part2(source: &str) -> String {
     (r.0 * r.1 * r.2).to_string()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
-
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source), "514579");
-    }
 
-    #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source), "241861950");
-    }
+    aoc_common::examples!(Solver);
 }
@@ -1,3 +1,5 @@
+use aoc_common::{Answer, Result, Solution};
+
 pub fn part1(source: &str) -> u32 {
     *add_load(source).iter().max().unwrap()
 }
@@ -9,6 +11,18 @@ pub fn part2(source: &str) -> u32 {
     load[load.len() - 3..].iter().sum()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 fn add_load(source: &str) -> Vec<u32> {
     source
         .split("\n\n")
@@ -18,19 +32,7 @@ fn add_load(source: &str) -> Vec<u32> {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
-
     use super::*;
 
-    #[test]
-    fn part1_test() {
-        let file = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&file), 24000);
-    }
-
-    #[test]
-    fn part2_test() {
-        let file = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&file), 45000);
-    }
+    aoc_common::examples!(Solver);
 }
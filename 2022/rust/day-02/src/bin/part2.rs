@@ -1,4 +1,4 @@
-use day_02::part2;
+use aoc_2022_day_02::part2;
 use std::fs;
 
 fn main() {
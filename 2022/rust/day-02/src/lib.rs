@@ -1,5 +1,5 @@
 use ::int_enum::IntEnum;
-use std::cmp::Ordering;
+use aoc_common::{Answer, Result, Solution};
 
 #[repr(u16)]
 #[derive(Debug, Copy, Clone, IntEnum)]
@@ -25,33 +25,22 @@ impl Hand {
             _ => unimplemented!("Unknown hand!"),
         }
     }
-}
-
-impl Eq for Hand {}
-impl PartialEq for Hand {
-    fn eq(&self, other: &Self) -> bool {
-        self.int_value().eq(&other.int_value())
-    }
-}
 
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> Ordering {
+    /// The hands beat each other in a circle, so there is no ordering to them.
+    fn beats(&self, other: &Self) -> bool {
         use Hand::*;
-        use Ordering::*;
-
-        match (*self, *other) {
-            (Rock, Scissors) => return Greater,
-            (Scissors, Rock) => return Less,
-            _ => (),
-        }
 
-        self.int_value().cmp(&other.int_value())
+        matches!(
+            (*self, *other),
+            (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper)
+        )
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl Eq for Hand {}
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.int_value().eq(&other.int_value())
     }
 }
 
@@ -67,13 +56,13 @@ impl Game {
     }
 
     fn resolve(&self) -> u16 {
-        use Ordering::*;
-
         let hand_value = self.1.int_value();
-        match self.1.cmp(&self.0) {
-            Equal => hand_value + 3,
-            Greater => hand_value + 6,
-            Less => hand_value,
+        if self.1 == self.0 {
+            hand_value + 3
+        } else if self.1.beats(&self.0) {
+            hand_value + 6
+        } else {
+            hand_value
         }
     }
 
@@ -113,6 +102,18 @@ pub fn part2(source: &str) -> String {
     parse(source, |g: Game| g.resolve_2().into())
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 fn parse(source: &str, resolver: impl Fn(Game) -> u32) -> String {
     source
         .lines()
@@ -125,9 +126,37 @@ fn parse(source: &str, resolver: impl Fn(Game) -> u32) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use proptest::prelude::*;
     use Hand::*;
 
+    aoc_common::examples!(Solver);
+
+    fn any_hand() -> impl Strategy<Value = Hand> {
+        prop_oneof![Just(Rock), Just(Paper), Just(Scissors)]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_hand_beats(a in any_hand(), b in any_hand()) {
+            // a draw, or exactly one of them wins
+            let outcomes = [a == b, a.beats(&b), b.beats(&a)];
+            prop_assert_eq!(outcomes.iter().filter(|&&o| o).count(), 1);
+        }
+
+        #[test]
+        fn prop_game_scores(a in any_hand(), b in any_hand()) {
+            // a win and a loss or two draws, 6 points in total
+            let scores = Game(a, b).resolve() + Game(b, a).resolve();
+            prop_assert_eq!(scores, a.int_value() + b.int_value() + 6);
+        }
+
+        #[test]
+        fn prop_game_parse(a in "[ABC]", b in "[XYZ]") {
+            let game = Game::from(&format!("{a} {b}"));
+            prop_assert_eq!((game.0, game.1), (Hand::from(&a), Hand::from(&b)));
+        }
+    }
+
     #[test]
     fn test_hand_creation() {
         assert_eq!(Hand::from("A"), Rock);
@@ -139,18 +168,18 @@ mod tests {
     }
 
     #[test]
-    fn test_hand_cmp() {
+    fn test_hand_beats() {
         assert!(Rock == Rock);
         assert!(Paper == Paper);
         assert!(Scissors == Scissors);
 
-        assert!(Paper > Rock);
-        assert!(Scissors > Paper);
-        assert!(Rock > Scissors);
+        assert!(Paper.beats(&Rock));
+        assert!(Scissors.beats(&Paper));
+        assert!(Rock.beats(&Scissors));
 
-        assert!(Rock < Paper);
-        assert!(Paper < Scissors);
-        assert!(Scissors < Rock);
+        assert!(!Rock.beats(&Paper));
+        assert!(!Paper.beats(&Scissors));
+        assert!(!Scissors.beats(&Rock));
 
         assert_eq!(Rock.int_value(), 1);
     }
@@ -187,16 +216,4 @@ mod tests {
         let game = Game(Scissors, Rock);
         assert_eq!(game.resolve(), 7);
     }
-
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source), "15");
-    }
-
-    #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source), "12");
-    }
 }
@@ -1,4 +1,4 @@
-use day_03::part2;
+use aoc_2022_day_03::part2;
 use std::fs;
 
 fn main() {
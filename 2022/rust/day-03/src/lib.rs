@@ -1,7 +1,7 @@
+use aoc_common::{Answer, Result, Solution};
 use itertools::Itertools;
 use std::collections::HashSet;
 
-
 pub fn part1(source: &str) -> String {
     source
         .lines()
@@ -34,6 +34,18 @@ pub fn part2(source: &str) -> String {
         .to_string()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 trait Priority {
     const LOW_BOUND: (u32, u32) = ('a' as u32, 'z' as u32);
     const HIGH_BOUND: (u32, u32) = ('A' as u32, 'Z' as u32);
@@ -93,19 +105,8 @@ macro_rules! uniq {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
-
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source), "157");
-    }
 
-    #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source), "70");
-    }
+    aoc_common::examples!(Solver);
 
     #[test]
     fn test_set_split() {
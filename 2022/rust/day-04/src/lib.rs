@@ -1,3 +1,5 @@
+use aoc_common::{Answer, Result, Solution};
+
 pub fn part1(source: &str) -> String {
     source
         .lines()
@@ -18,6 +20,18 @@ pub fn part2(source: &str) -> String {
         .to_string()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 struct Area {
     left: u32,
     right: u32,
@@ -61,18 +75,25 @@ impl Splitter for str {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use proptest::prelude::*;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source), "2");
+    aoc_common::examples!(Solver);
+
+    fn any_area() -> impl Strategy<Value = (u32, u32)> {
+        (1..100u32, 0..20u32).prop_map(|(left, len)| (left, left + len))
     }
 
-    #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source), "4");
+    proptest! {
+        #[test]
+        fn prop_area_overlap((a, b) in any_area(), (c, d) in any_area()) {
+            let (x, y) = (Area::from(&format!("{a}-{b}")), Area::from(&format!("{c}-{d}")));
+            prop_assert_eq!(x.overlap(&y), y.overlap(&x));
+            prop_assert_eq!(x.either_contains(&y), y.either_contains(&x));
+
+            // containing is a kind of overlapping, and both agree with the sections themselves
+            prop_assert!(!x.either_contains(&y) || x.overlap(&y));
+            prop_assert_eq!(x.overlap(&y), (a..=b).any(|s| (c..=d).contains(&s)));
+        }
     }
 
     #[test]
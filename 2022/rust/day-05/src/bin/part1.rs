@@ -1,4 +1,4 @@
-use day_05::part1;
+use aoc_2022_day_05::part1;
 use std::fs;
 
 fn main() {
@@ -1,35 +1,73 @@
-use std::collections::VecDeque;
+use aoc_common::{Answer, Frame, Recorder, Result, Solution};
+use std::{collections::VecDeque, hint::black_box};
 
 pub fn part1(source: &str) -> String {
-    let (mut stacks, moves) = parse(source);
-
-    solve(moves, |(times, from, to)| {
-        for _ in 0..times {
-            let v = stacks[from].pop_front().unwrap();
-            stacks[(to)].push_front(v);
-        }
-    });
-
-    report(&mut stacks)
+    crane(source, false, None)
 }
 
 pub fn part2(source: &str) -> String {
+    crane(source, true, None)
+}
+
+/// Moves the crates one by one, or all of a move at once like the CrateMover 9001, drawing the stacks after every move.
+fn crane(source: &str, at_once: bool, mut recorder: Option<&mut dyn Recorder>) -> String {
     let (mut stacks, moves) = parse(source);
+    if let Some(r) = recorder.as_deref_mut() {
+        r.record(Frame::new(&draw(&stacks)));
+    }
 
     solve(moves, |(times, from, to)| {
-        let mut r = VecDeque::new();
-        for _ in 0..times {
-            let v = stacks[from].pop_front().unwrap();
-            r.push_back(v);
+        if at_once {
+            let mut r = VecDeque::new();
+            for _ in 0..times {
+                let v = stacks[from].pop_front().unwrap();
+                r.push_back(v);
+            }
+            while let Some(i) = r.pop_back() {
+                stacks[to].push_front(i);
+            }
+        } else {
+            for _ in 0..times {
+                let v = stacks[from].pop_front().unwrap();
+                stacks[to].push_front(v);
+            }
         }
-        while let Some(i) = r.pop_back() {
-            stacks[(to)].push_front(i);
+
+        if let Some(r) = recorder.as_deref_mut() {
+            let caption = format!("move {times} from {} to {}", from + 1, to + 1);
+            r.record(Frame::new(&draw(&stacks)).with_caption(caption));
         }
     });
 
     report(&mut stacks)
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        black_box(parse(input));
+        Some(Ok(()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+
+    fn visualize(
+        &self,
+        part: u8,
+        input: &str,
+        recorder: &mut dyn Recorder,
+    ) -> Option<Result<Answer>> {
+        matches!(part, 1 | 2).then(|| Ok(crane(input, part == 2, Some(recorder)).into()))
+    }
+}
+
 fn report(stacks: &mut [VecDeque<char>]) -> String {
     stacks
         .iter_mut()
@@ -37,6 +75,31 @@ fn report(stacks: &mut [VecDeque<char>]) -> String {
         .collect::<String>()
 }
 
+/// The stacks the way the puzzle draws them, tops up and numbered at the bottom.
+fn draw(stacks: &[VecDeque<char>]) -> String {
+    let height = stacks.iter().map(VecDeque::len).max().unwrap_or_default();
+
+    let mut picture = String::new();
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|s| match s.len().checked_sub(level + 1).map(|i| s[i]) {
+                Some(c) => format!("[{c}]"),
+                None => "   ".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        picture += row.trim_end();
+        picture += "\n";
+    }
+
+    let numbers = (1..=stacks.len())
+        .map(|n| format!(" {n} "))
+        .collect::<Vec<_>>()
+        .join(" ");
+    picture + numbers.trim_end()
+}
+
 fn solve(moves: &str, solver: impl FnMut((usize, usize, usize))) {
     moves.lines().map(parse_move).for_each(solver)
 }
@@ -51,7 +114,7 @@ fn make_stacks(source: &mut std::str::Split<&str>) -> Vec<VecDeque<char>> {
         .for_each(|line| {
             line.chars()
                 .enumerate()
-                .filter(|(_, c)| ('A'..='Z').contains(c))
+                .filter(|(_, c)| c.is_ascii_uppercase())
                 .for_each(|(i, c)| {
                     if (1..).step_by(4).any(|n| n == i) {
                         debug_assert_eq!((i - 1) % 4, 0);
@@ -93,18 +156,26 @@ fn parse(source: &str) -> (Vec<VecDeque<char>>, &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use aoc_common::frames::Frames;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source), "CMZ");
-    }
+    aoc_common::examples!(Solver);
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source), "MCD");
+    fn test_draw() {
+        let source = include_str!("../test_input.txt");
+        let (stacks, _) = parse(source);
+        assert_eq!(draw(&stacks), "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3");
+
+        let mut frames = Frames::default();
+        assert_eq!(
+            Solver.visualize(2, source, &mut frames).unwrap().unwrap(),
+            Answer::from("MCD")
+        );
+        assert_eq!(frames.0.len(), 5);
+        assert_eq!(frames.0[4].caption, "move 1 from 1 to 2");
+        assert_eq!(frames.0[4].lines[0], "        [D]");
+
+        assert!(Solver.visualize(3, source, &mut frames).is_none());
     }
 
     #[test]
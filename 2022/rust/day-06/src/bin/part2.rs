@@ -1,4 +1,4 @@
-use day_06::part2;
+use aoc_2022_day_06::part2;
 use std::fs;
 
 fn main() {
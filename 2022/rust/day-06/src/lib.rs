@@ -1,3 +1,4 @@
+use aoc_common::{Answer, Result, Solution};
 use std::collections::BTreeSet;
 
 pub fn part1(source: &str) -> String {
@@ -8,6 +9,18 @@ pub fn part2(source: &str) -> String {
     find_start_distinct(source, 14).to_string()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 fn find_start_distinct(source: &str, window: usize) -> usize {
     source
         .as_bytes()
@@ -24,36 +37,5 @@ fn find_start_distinct(source: &str, window: usize) -> usize {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part1() {
-        let i = "bvwbjplbgvbhsrlpgdmjqwftvncz";
-        assert_eq!(part1(i), "5");
-
-        let i = "nppdvjthqldpwncqszvftbrmjlhg";
-        assert_eq!(part1(i), "6");
-
-        let i = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
-        assert_eq!(part1(i), "10");
-
-        let i = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
-        assert_eq!(part1(i), "11");
-    }
-
-    #[test]
-    fn test_part2() {
-        let i = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-        assert_eq!(part2(i), "19");
-
-        let i = "bvwbjplbgvbhsrlpgdmjqwftvncz";
-        assert_eq!(part2(i), "23");
-
-        let i = "nppdvjthqldpwncqszvftbrmjlhg";
-        assert_eq!(part2(i), "23");
-
-        let i = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
-        assert_eq!(part2(i), "29");
-
-        let i = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
-        assert_eq!(part2(i), "26");
-    }
+    aoc_common::examples!(Solver);
 }
@@ -1,7 +1,12 @@
-use day_07::part1;
+use aoc_2022_day_07::part1;
+use color_eyre::Result;
 use std::fs;
 
-fn main() {
+fn main() -> Result<()> {
+    color_eyre::install().unwrap();
+    aoc_common::log::init(0);
+
     let file = fs::read_to_string("./real_input.txt").unwrap();
-    println!("{}", part1(&file));
+    println!("{}", part1(&file)?);
+    Ok(())
 }
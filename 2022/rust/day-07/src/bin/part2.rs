@@ -1,11 +1,14 @@
+use aoc_2022_day_07::part2;
+use aoc_common::Params;
 use color_eyre::Result;
-use day_07::part2;
-use std::fs;
+use std::{fs, path::Path};
 
 fn main() -> Result<()> {
     color_eyre::install().unwrap();
+    aoc_common::log::init(0);
 
     let file = fs::read_to_string("./real_input.txt").unwrap();
-    println!("{}", part2(&file)?);
+    let params = Params::load(Path::new("."), Path::new("./real_input.txt"))?;
+    println!("{}", part2(&file, &params)?);
     Ok(())
 }
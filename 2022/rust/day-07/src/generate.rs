@@ -0,0 +1,152 @@
+//! Random terminal transcripts, `size` is the number of files, at least one.
+//!
+//! Like in the real input, most files are small enough for part 1 to find directories below 100000.
+//! A few big ones make the total between 40 and 70 million, so that part 2 has something to delete on the usual
+//! 70 million disk.
+
+use std::collections::HashSet;
+
+use aoc_common::rand::Rng;
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<(u64, String)>,
+    /// Names of the entries, files and directories alike.
+    taken: HashSet<String>,
+}
+
+impl Dir {
+    fn name(&mut self, rng: &mut impl Rng, file: bool) -> String {
+        loop {
+            let mut name: String = (0..rng.gen_range(1..=8))
+                .map(|_| char::from(b'a' + rng.gen_range(0..26)))
+                .collect();
+            if file && rng.gen_bool(0.5) {
+                name += [".txt", ".dat", ".log", ".lst"][rng.gen_range(0..4)];
+            }
+
+            if self.taken.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    // there is always at least one big file
+    let size = size.max(1);
+    let mut dirs = vec![Dir {
+        name: "/".to_string(),
+        ..Default::default()
+    }];
+
+    // about one directory per five files, each under a random earlier one
+    for i in 1..size.div_ceil(5) {
+        let parent = rng.gen_range(0..i);
+        let name = dirs[parent].name(rng, false);
+        dirs[parent].dirs.push(i);
+        dirs.push(Dir {
+            name,
+            ..Default::default()
+        });
+    }
+
+    // spread evenly over the orders of magnitude, so most of them are a few thousand
+    let big = size.div_ceil(50);
+    let mut sizes: Vec<u64> = (big..size)
+        .map(|_| 10f64.powf(rng.gen_range(3.0..5.5)) as u64)
+        .collect();
+    let total = rng.gen_range(41_000_000..69_000_000);
+
+    // the small ones take at most half of the total, even when there are thousands of them
+    let sum = sizes.iter().sum::<u64>().max(1);
+    if sum > total / 2 {
+        sizes
+            .iter_mut()
+            .for_each(|s| *s = (*s * (total / 2) / sum).max(1));
+    }
+    let rest = total - sizes.iter().sum::<u64>();
+    sizes.extend((0..big).map(|_| rest / big as u64));
+
+    for s in sizes {
+        let d = rng.gen_range(0..dirs.len());
+        let name = dirs[d].name(rng, true);
+        dirs[d].files.push((s, name));
+    }
+
+    let mut res = "$ cd /\n".to_string();
+    transcript(&dirs, 0, &mut res);
+    res
+}
+
+fn transcript(dirs: &[Dir], dir: usize, res: &mut String) {
+    let d = &dirs[dir];
+    *res += "$ ls\n";
+    for &sub in &d.dirs {
+        *res += &format!("dir {}\n", dirs[sub].name);
+    }
+    for (size, name) in &d.files {
+        *res += &format!("{size} {name}\n");
+    }
+
+    for &sub in &d.dirs {
+        *res += &format!("$ cd {}\n", dirs[sub].name);
+        transcript(dirs, sub, res);
+        *res += "$ cd ..\n";
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+    use aoc_common::{
+        rand::{rngs::StdRng, SeedableRng},
+        Params,
+    };
+
+    #[test]
+    fn test_generated() {
+        let params = Params::new()
+            .with("disk", 70_000_000)
+            .with("needed", 30_000_000);
+
+        let mut found = 0;
+        for seed in 0..20 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 200);
+            let sizes: Vec<u64> = source
+                .lines()
+                .filter_map(|l| l.split_once(' ')?.0.parse().ok())
+                .collect();
+            assert_eq!(sizes.len(), 200);
+            assert!((40_000_000..70_000_000).contains(&sizes.iter().sum::<u64>()));
+            if part1(&source).unwrap() != "0" {
+                found += 1;
+            }
+            part2(&source, &params).unwrap();
+        }
+        // a directory may still end up with a big file in every seed
+        assert!(
+            found > 10,
+            "only {found} seeds had small enough directories"
+        );
+
+        let small = (0..20)
+            .filter(|&seed| part1(&input(&mut StdRng::seed_from_u64(seed), 10)).unwrap() != "0");
+        assert!(small.count() > 0);
+
+        for size in [0, 1] {
+            let source = input(&mut StdRng::seed_from_u64(0), size);
+            let sizes: Vec<u64> = source
+                .lines()
+                .filter_map(|l| l.split_once(' ')?.0.parse().ok())
+                .collect();
+            assert_eq!(sizes.len(), 1);
+            assert!((40_000_000..70_000_000).contains(&sizes[0]));
+            part1(&source).unwrap();
+            part2(&source, &params).unwrap();
+        }
+    }
+}
@@ -1,13 +1,21 @@
+pub mod generate;
 mod parser;
 
+use aoc_common::{
+    parse::finish,
+    rand::{rngs::StdRng, SeedableRng},
+    tracing::{debug, enabled, trace, Level},
+    Answer, Params, Solution,
+};
 use camino::Utf8PathBuf;
 use color_eyre::Result;
 use id_tree::{InsertBehavior, Node, Tree};
-use nom::{combinator::all_consuming, Finish};
+use nom::combinator::all_consuming;
 use parser::*;
+use std::hint::black_box;
 
-pub fn part1(source: &str) -> String {
-    let tree = create_tree(source).unwrap();
+pub fn part1(source: &str) -> Result<String> {
+    let tree = create_tree(source)?;
 
     let sum = tree
         .traverse_pre_order(tree.root_node_id().unwrap())
@@ -15,22 +23,21 @@ pub fn part1(source: &str) -> String {
         .filter(|n| !n.children().is_empty())
         .map(|n| total_size(&tree, n).unwrap())
         .filter(|&s| s <= 100_000)
-        .inspect(|s| {
-            dbg!(s);
-        })
+        .inspect(|s| debug!(size = s, "small enough directory"))
         .sum::<u64>()
         .to_string();
 
-    sum
+    Ok(sum)
 }
 
-pub fn part2(source: &str) -> Result<String> {
-    let tree = create_tree(source).unwrap();
+pub fn part2(source: &str, params: &Params) -> Result<String> {
+    let tree = create_tree(source)?;
 
-    let total_space = 70000000_u64;
+    let total_space = params.optional("disk").unwrap_or(70_000_000) as u64;
     let used_space = total_size(&tree, tree.get(tree.root_node_id().unwrap())?)?;
-    let free_space = total_space.checked_sub(dbg!(used_space)).unwrap();
-    let needed_free_space = 30000000_u64;
+    debug!(used_space);
+    let free_space = total_space.checked_sub(used_space).unwrap();
+    let needed_free_space = params.optional("needed").unwrap_or(30_000_000) as u64;
     let minimum_space_to_free = needed_free_space.checked_sub(free_space).unwrap();
 
     let min = tree
@@ -38,15 +45,39 @@ pub fn part2(source: &str) -> Result<String> {
         .filter(|n| !n.children().is_empty())
         .map(|n| total_size(&tree, n).unwrap())
         .filter(|&s| s >= minimum_space_to_free)
-        .inspect(|s| {
-            dbg!(s);
-        })
+        .inspect(|s| debug!(size = s, "big enough directory"))
         .min()
         .unwrap()
         .to_string();
     Ok(min)
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse(input).map(|lines| {
+            black_box(lines);
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        self.part2_with(input, &Params::new())
+    }
+
+    fn part2_with(&self, input: &str, params: &Params) -> Result<Answer> {
+        Ok(part2(input, params)?.into())
+    }
+}
+
 #[derive(Debug)]
 struct FsEntry {
     #[allow(dead_code)]
@@ -54,10 +85,15 @@ struct FsEntry {
     size: u64,
 }
 
-fn create_tree(source: &str) -> Result<Tree<FsEntry>> {
-    let lines = source
+fn parse(source: &str) -> Result<Vec<Line>> {
+    source
         .lines()
-        .map(|l| all_consuming(parse_line)(l).finish().unwrap().1);
+        .map(|l| finish(source, all_consuming(parse_line)(l)))
+        .collect()
+}
+
+fn create_tree(source: &str) -> Result<Tree<FsEntry>> {
+    let lines = parse(source)?;
 
     let mut tree = Tree::<FsEntry>::new();
     let root = tree.insert(
@@ -103,9 +139,11 @@ fn create_tree(source: &str) -> Result<Tree<FsEntry>> {
         }
     }
 
-    let mut s = String::new();
-    tree.write_formatted(&mut s)?;
-    println!("{s}");
+    if enabled!(Level::TRACE) {
+        let mut s = String::new();
+        tree.write_formatted(&mut s)?;
+        trace!("the file system:\n{s}");
+    }
     Ok(tree)
 }
 
@@ -120,17 +158,25 @@ fn total_size(tree: &Tree<FsEntry>, node: &Node<FsEntry>) -> Result<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use proptest::prelude::*;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source), "95437");
+    aoc_common::examples!(Solver);
+
+    proptest! {
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = parse(&source);
+        }
+
+        #[test]
+        fn prop_parse_no_panic_near_miss(source in "(\\$ (ls|cd [a-z./]{0,4})|dir [a-z]{0,3}|[0-9]{1,25} [a-z.]{0,4}|[a-z$ ]{0,5})(\n[a-z0-9$ ./]{0,8}){0,5}") {
+            let _ = parse(&source);
+        }
     }
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "24933642");
+    fn test_parse_error() {
+        let e = part1("$ cd /\n$ ls\n12a b.txt").unwrap_err();
+        assert_eq!(e.to_string(), "Expected \" \" at line 3, column 3");
     }
 }
@@ -1,13 +1,13 @@
+use aoc_common::parse::{token, IResult};
 use camino::Utf8PathBuf;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while1},
+    bytes::complete::take_while1,
     combinator::map,
     sequence::{preceded, separated_pair},
-    IResult,
 };
 
-fn parse_path(i: &str) -> IResult<&str, Utf8PathBuf> {
+fn parse_path(i: &str) -> IResult<'_, Utf8PathBuf> {
     map(
         take_while1(|c: char| "abcdefghijklmnopqrstuvwxyz./".contains(c)),
         Into::into,
@@ -17,15 +17,15 @@ fn parse_path(i: &str) -> IResult<&str, Utf8PathBuf> {
 #[derive(Debug)]
 struct Ls;
 
-fn parse_ls(i: &str) -> IResult<&str, Ls> {
-    map(tag("ls"), |_| Ls)(i)
+fn parse_ls(i: &str) -> IResult<'_, Ls> {
+    map(token("ls"), |_| Ls)(i)
 }
 
 #[derive(Debug)]
 struct Cd(Utf8PathBuf);
 
-fn parse_cd(i: &str) -> IResult<&str, Cd> {
-    map(preceded(tag("cd "), parse_path), Cd)(i)
+fn parse_cd(i: &str) -> IResult<'_, Cd> {
+    map(preceded(token("cd "), parse_path), Cd)(i)
 }
 
 #[derive(Debug)]
@@ -46,23 +46,24 @@ impl From<Cd> for Command {
     }
 }
 
-fn parse_command(i: &str) -> IResult<&str, Command> {
-    let (i, _) = tag("$ ")(i)?;
+fn parse_command(i: &str) -> IResult<'_, Command> {
+    let (i, _) = token("$ ")(i)?;
     alt((map(parse_ls, Into::into), map(parse_cd, Into::into)))(i)
 }
 
 #[derive(Debug)]
 pub enum Entry {
+    #[allow(dead_code)]
     Dir(Utf8PathBuf),
     File(u64, Utf8PathBuf),
 }
 
-fn parse_entry(i: &str) -> IResult<&str, Entry> {
+fn parse_entry(i: &str) -> IResult<'_, Entry> {
     let parse_file = map(
-        separated_pair(nom::character::complete::u64, tag(" "), parse_path),
+        separated_pair(nom::character::complete::u64, token(" "), parse_path),
         |(size, path)| Entry::File(size, path),
     );
-    let parse_dir = map(preceded(tag("dir "), parse_path), Entry::Dir);
+    let parse_dir = map(preceded(token("dir "), parse_path), Entry::Dir);
 
     alt((parse_file, parse_dir))(i)
 }
@@ -73,7 +74,7 @@ pub enum Line {
     Entry(Entry),
 }
 
-pub fn parse_line(i: &str) -> IResult<&str, Line> {
+pub fn parse_line(i: &str) -> IResult<'_, Line> {
     alt((
         map(parse_command, Line::Command),
         map(parse_entry, Line::Entry),
@@ -83,6 +84,28 @@ pub fn parse_line(i: &str) -> IResult<&str, Line> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_parse_line_roundtrip(name in "[a-z.]{1,10}", size in any::<u64>(), kind in 0..4) {
+            let line = match kind {
+                0 => "$ ls".to_string(),
+                1 => format!("$ cd {name}"),
+                2 => format!("dir {name}"),
+                _ => format!("{size} {name}"),
+            };
+
+            let (rest, parsed) = parse_line(&line).unwrap();
+            prop_assert_eq!(rest, "");
+            match (kind, parsed) {
+                (0, Line::Command(Command::Ls)) => {}
+                (1, Line::Command(Command::Cd(path))) | (2, Line::Entry(Entry::Dir(path))) => prop_assert_eq!(path, name),
+                (3, Line::Entry(Entry::File(s, path))) => prop_assert_eq!((s, path.as_str()), (size, name.as_str())),
+                (_, parsed) => prop_assert!(false, "{line:?} parsed as {parsed:?}"),
+            }
+        }
+    }
 
     #[test]
     fn test_parse_line() {
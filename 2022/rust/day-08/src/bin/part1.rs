@@ -1,5 +1,5 @@
+use aoc_2022_day_08::part1;
 use color_eyre::Result;
-use day_08::part1;
 use std::fs;
 
 fn main() -> Result<()> {
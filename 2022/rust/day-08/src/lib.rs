@@ -1,135 +1,88 @@
+use aoc_common::{eyre::eyre, Answer, Solution};
+use aoc_grid::{Direction, Grid, Pos};
 use color_eyre::Result;
-use itertools::rev;
+use std::hint::black_box;
 
 pub fn part1(source: &str) -> Result<String> {
-    let data = Data(parse_input(source));
+    let data = Data(parse_input(source)?);
 
-    let mut r: u32 = 0;
-
-    for row in 0..data.len() {
-        for column in 0..data.len() {
-            if data.is_visible(row, column) {
-                r += 1;
-            }
-        }
-    }
+    let r = data.0.positions().filter(|&p| data.is_visible(p)).count();
 
     Ok(r.to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
-    let data = Data(parse_input(source));
+    let data = Data(parse_input(source)?);
 
     let res = data
         .0
         .iter()
-        .enumerate()
-        .map(|(x, row)| {
-            row.iter()
-                .enumerate()
-                .map(|(y, column)| data.get_scenic_score(column, x, y))
-                .max()
-                .unwrap()
-        })
+        .map(|(p, tree)| data.get_scenic_score(tree, p))
         .max()
         .unwrap();
 
     Ok(res.to_string())
 }
 
-fn parse_input(source: &str) -> Vec<Vec<u8>> {
-    source
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_string().parse::<u8>().unwrap())
-                .collect::<Vec<u8>>()
-        })
-        .collect()
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse_input(input).map(|g| {
+            black_box(g);
+        }))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+fn parse_input(source: &str) -> Result<Grid<u8>> {
+    Grid::parse(source, |c| {
+        c.to_digit(10)
+            .map(|d| d as u8)
+            .ok_or_else(|| eyre!("Not a tree height: {c}"))
+    })
 }
 
-struct Data(Vec<Vec<u8>>);
+struct Data(Grid<u8>);
 impl Data {
-    fn len(&self) -> usize {
-        self.0.len()
+    fn get_scenic_score(&self, curr_value: &u8, pos: Pos) -> u32 {
+        Direction::ALL
+            .into_iter()
+            .map(|dir| {
+                let mut r = 0;
+                for (_, tree) in self.0.ray(pos, dir.vector()) {
+                    r += 1;
+                    if tree >= curr_value {
+                        break;
+                    }
+                }
+                r
+            })
+            .product()
     }
 
-    fn get_scenic_score(&self, curr_value: &u8, x: usize, y: usize) -> u32 {
-        let mut r_top = 0;
-        for i in rev(0..x) {
-            r_top += 1;
-            if self.0[i][y] >= *curr_value {
-                break;
-            }
-        }
-
-        let mut r_bottom = 0;
-        for i in (x + 1)..self.len() {
-            r_bottom += 1;
-            if self.0[i][y] >= *curr_value {
-                break;
-            }
-        }
-
-        let mut r_left = 0;
-        for i in rev(0..y) {
-            r_left += 1;
-            if self.0[x][i] >= *curr_value {
-                break;
-            }
-        }
-
-        let mut r_right = 0;
-        for i in (y + 1)..self.len() {
-            r_right += 1;
-            if self.0[x][i] >= *curr_value {
-                break;
-            }
-        }
-
-        r_top * r_bottom * r_right * r_left
+    fn is_edge(&self, pos: Pos) -> bool {
+        self.0.neighbors4(pos).count() < 4
     }
 
-    fn is_edge(&self, x: usize, y: usize) -> bool {
-        x == 0 || y == 0 || x == self.len() - 1 || y == self.len() - 1
+    fn is_visible(&self, pos: Pos) -> bool {
+        self.is_edge(pos) || !self.check_sides(pos)
     }
 
-    fn is_visible(&self, x: usize, y: usize) -> bool {
-        self.is_edge(x, y) || !self.check_sides(x, y)
-    }
+    /// Whether there is a taller (or as tall) tree in every direction.
+    fn check_sides(&self, pos: Pos) -> bool {
+        let curr = self.0[pos];
 
-    fn check_sides(&self, x: usize, y: usize) -> bool {
-        let curr = self.0[x][y];
-
-        let mut taller_top = false;
-        for i in 0..x {
-            if self.0[i][y] >= curr {
-                taller_top = true;
-            }
-        }
-
-        let mut taller_bottom = false;
-        for i in (x + 1)..self.len() {
-            if self.0[i][y] >= curr {
-                taller_bottom = true;
-            }
-        }
-
-        let mut taller_left = false;
-        for i in 0..y {
-            if self.0[x][i] >= curr {
-                taller_left = true;
-            }
-        }
-
-        let mut taller_right = false;
-        for i in (y + 1)..self.len() {
-            if self.0[x][i] >= curr {
-                taller_right = true;
-            }
-        }
-
-        taller_top && taller_bottom && taller_left && taller_right
+        Direction::ALL
+            .into_iter()
+            .all(|dir| self.0.ray(pos, dir.vector()).any(|(_, &tree)| tree >= curr))
     }
 }
 
@@ -138,57 +91,59 @@ mod tests {
     use super::*;
     use std::fs;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "21");
-    }
+    aoc_common::examples!(Solver);
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "8");
+    fn test_data_sides() {
+        let d = Data(parse_input("123\n456\n789").unwrap());
+        assert!(d.is_visible(Pos::new(1, 1)));
     }
 
     #[test]
-    fn test_data_sides() {
-        let d = Data(parse_input("123\n456\n789"));
-        assert!(d.is_visible(1, 1));
+    fn test_data_edge() {
+        let d = Data(parse_input("123\n456\n789").unwrap());
+        assert_eq!(d.0.height(), 3);
+        assert!(d.is_edge(Pos::new(0, 0)));
+        assert!(d.is_edge(Pos::new(1, 0)));
+        assert!(d.is_edge(Pos::new(2, 0)));
+        assert!(d.is_edge(Pos::new(0, 0)));
+        assert!(d.is_edge(Pos::new(0, 1)));
+        assert!(d.is_edge(Pos::new(0, 2)));
+        assert!(!d.is_edge(Pos::new(1, 1)));
+        assert!(d.is_edge(Pos::new(1, 2)));
+        assert!(d.is_edge(Pos::new(2, 2)));
+        assert!(d.is_edge(Pos::new(2, 1)));
     }
 
     #[test]
-    fn test_data_edge() {
-        let d = Data(parse_input("123\n456\n789"));
-        assert_eq!(d.len(), 3);
-        assert!(d.is_edge(0, 0));
-        assert!(d.is_edge(0, 1));
-        assert!(d.is_edge(0, 2));
-        assert!(d.is_edge(0, 0));
-        assert!(d.is_edge(1, 0));
-        assert!(d.is_edge(2, 0));
-        assert!(!d.is_edge(1, 1));
-        assert!(d.is_edge(2, 1));
-        assert!(d.is_edge(2, 2));
-        assert!(d.is_edge(1, 2));
+    fn test_data_rectangular() {
+        let d = Data(parse_input("1111\n1911\n1111").unwrap());
+        assert!(d.is_visible(Pos::new(1, 1)));
+        assert!(!d.is_visible(Pos::new(2, 1)));
+        assert!(d.is_edge(Pos::new(3, 1)));
     }
 
     #[test]
     fn test_scenic_score() {
         let source = fs::read_to_string("./test_input.txt").unwrap();
-        let d = Data(parse_input(&source));
-        assert_eq!(d.get_scenic_score(&5, 1, 2), 4);
-        assert_eq!(d.get_scenic_score(&5, 3, 2), 8);
+        let d = Data(parse_input(&source).unwrap());
+        assert_eq!(d.get_scenic_score(&5, Pos::new(2, 1)), 4);
+        assert_eq!(d.get_scenic_score(&5, Pos::new(2, 3)), 8);
     }
 
     #[test]
     fn test_parse_input() {
         let i = "123";
-        assert_eq!(parse_input(i), vec![vec![1, 2, 3]]);
+        assert_eq!(
+            parse_input(i).unwrap(),
+            Grid::from_rows(vec![vec![1, 2, 3]]).unwrap()
+        );
+        assert!(parse_input("12a").is_err());
     }
 
     #[test]
     fn test_rev_iter() {
-        let mut q = rev(0..3);
+        let mut q = (0..3).rev();
         assert_eq!(q.next().unwrap(), 2);
     }
 }
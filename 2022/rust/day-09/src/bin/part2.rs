@@ -1,9 +1,10 @@
+use aoc_2022_day_09::part2;
 use color_eyre::Result;
-use day_09::part2;
 use std::fs;
 
 fn main() -> Result<()> {
     color_eyre::install().unwrap();
+    aoc_common::log::init(0);
 
     let file = fs::read_to_string("./real_input.txt").unwrap();
     println!("{}", part2(&file)?);
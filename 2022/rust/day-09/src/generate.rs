@@ -0,0 +1,45 @@
+//! Random head movements, `size` is the number of moves.
+//!
+//! The distances stay short like in the real inputs, so the rope keeps folding onto itself.
+
+use aoc_common::rand::Rng;
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}",
+                ['U', 'D', 'L', 'R'][rng.gen_range(0..4)],
+                rng.gen_range(1..=20)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2};
+    use aoc_common::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..20 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 200);
+            let moves = parse(&source).unwrap();
+            assert_eq!(moves.len(), 200);
+            assert!(moves.iter().all(|m| (1..=20).contains(&m.1)));
+
+            // the rope folds onto itself, so the tail visits far fewer cells than the head takes steps,
+            // and the tail of the longer rope fewer still
+            let steps = moves.iter().map(|m| m.1 as usize).sum::<usize>();
+            let short: usize = part1(&source).unwrap().parse().unwrap();
+            let long: usize = part2(&source).unwrap().parse().unwrap();
+            assert!(
+                0 < long && long < short && short < steps,
+                "{long} {short} {steps}"
+            );
+        }
+    }
+}
@@ -1,35 +1,45 @@
+pub mod generate;
 mod other;
 
+use aoc_common::{
+    diff::{first_divergence, Divergence},
+    geometry::{Direction, Point2, Vec2},
+    parse::{finish, token, IResult},
+    rand::{rngs::StdRng, SeedableRng},
+    tracing::trace,
+    Answer, Frame, Recorder, Solution,
+};
+use aoc_grid::{Grid, Pos};
+use std::hint::black_box;
 use indexmap::IndexSet as HashSet;
 use color_eyre::Result;
-use itertools::Itertools;
-use nom::{IResult, combinator::map, sequence::separated_pair, bytes::complete::tag, character::{complete::alpha1, self}};
+use nom::{combinator::{all_consuming, map_opt}, sequence::separated_pair, character::{complete::anychar, self}};
+
+fn parse(source: &str) -> Result<Vec<Instruction>> {
+    source.lines().map(|l| finish(source, all_consuming(parse_instruction)(l))).collect()
+}
 
 pub fn part1(source: &str) -> Result<String> {
     let mut grid = Data::new();
 
-    source
-        .lines()
-        .map(parse_instruction)
-        .map(|i| i.unwrap().1)
+    parse(source)?
+        .into_iter()
         .for_each(|i| grid.apply_move(i));
 
     // dbg!(&grid);
 
-    println!("{:?}", &grid.t_points);
+    trace!(points = ?grid.t_points, "visited by the tail");
     Ok((grid.t_points.len()).to_string())
 }
 
 // TODO fix my solution
 #[allow(unreachable_code)]
 pub fn part2(source: &str) -> Result<String> {
-    return Ok(other::solve_part2(source));
+    return other::solve_part2(source);
     let mut grid = Data::longer();
 
-    source
-        .lines()
-        .map(parse_instruction)
-        .map(|i| i.unwrap().1)
+    parse(source)?
+        .into_iter()
         .for_each(|i| grid.apply_move(i));
 
     // dbg!(&grid);
@@ -38,11 +48,140 @@ pub fn part2(source: &str) -> Result<String> {
     Ok((grid.t_points.len()).to_string())
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse(input).map(|instructions| {
+            black_box(instructions);
+        }))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn visualize(&self, part: u8, input: &str, recorder: &mut dyn Recorder) -> Option<Result<Answer>> {
+        match part {
+            1 => Some(watch(input, Data::new(), recorder).map(Answer::from)),
+            // the frames show where my rope goes, the answer stays the borrowed one of `part2` until it is fixed
+            2 => Some(watch(input, Data::longer(), recorder).and_then(|_| Ok(part2(input)?.into()))),
+            _ => None,
+        }
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+}
+
+/// Runs my part 2 rope and the borrowed one side by side, one step of the head at a time,
+/// and reports the first step after which their knots are not in the same places.
+pub fn compare_part2(source: &str) -> Result<Option<Divergence<Vec<Point2<i32>>>>> {
+    let instructions = parse(source)?;
+    // the move each step belongs to
+    let steps: Vec<(usize, Direction)> = instructions
+        .iter()
+        .enumerate()
+        .flat_map(|(n, i)| std::iter::repeat_n((n, i.0), i.1 as usize))
+        .collect();
+
+    let mut own = Data::longer();
+    let own_knots = steps.iter().map(|&(_, d)| {
+        own.apply_move(Instruction(d, 1));
+        own.rope.clone()
+    });
+
+    let mut other = other::Rope::new(10);
+    let other_knots = steps.iter().map(|&(_, d)| {
+        other.step(d.vector());
+        other.0.clone()
+    });
+
+    Ok(first_divergence(own_knots, other_knots).map(|d| {
+        let (n, _) = steps[d.step];
+        let first = steps.iter().position(|&(m, _)| m == n).unwrap();
+        let line = source.lines().nth(n).unwrap_or_default();
+        let label = format!("move {} `{line}`, step {} of it", n + 1, d.step - first + 1);
+        d.with_label(label)
+    }))
+}
+
+/// Pulls my rope through the moves and draws it after every one, the answer is how many places its tail visited.
+fn watch(source: &str, mut rope: Data, recorder: &mut dyn Recorder) -> Result<usize> {
+    let mut camera = Camera::default();
+
+    for (line, i) in source.lines().zip(parse(source)?) {
+        rope.apply_move(i);
+
+        let knots: Vec<Pos> = rope.rope.iter().map(screen).collect();
+        let (min, max) = camera.follow(knots[0]);
+        recorder.record(Frame::new(&draw(&knots, rope.t_points.iter().map(screen), min, max)).with_caption(line));
+    }
+
+    Ok(rope.t_points.len())
+}
+
+/// Where a knot of mine is drawn.
+fn screen(p: &Point2<i32>) -> Pos {
+    Pos::new(p.x as isize, p.y as isize)
+}
+
+/// The part of the plane the frames show, the real ropes wander much further than fits on a screen.
+/// It only moves once the head gets close to its edge.
+#[derive(Default)]
+struct Camera(Pos);
+
+impl Camera {
+    const SIZE: (isize, isize) = (60, 30);
+
+    fn follow(&mut self, head: Pos) -> (Pos, Pos) {
+        let (width, height) = Self::SIZE;
+        let min = &mut self.0;
+
+        if head.x < min.x + 2 || head.x > min.x + width - 3 {
+            min.x = head.x - width / 2;
+        }
+        if head.y < min.y + 2 || head.y > min.y + height - 3 {
+            min.y = head.y - height / 2;
+        }
+
+        (*min, Pos::new(min.x + width - 1, min.y + height - 1))
+    }
+}
+
+/// The rope between `min` and `max` the way the puzzle draws it: `H` and the numbered knots (or `T`),
+/// over the start `s` and the places the tail has been `#`.
+fn draw(knots: &[Pos], visited: impl IntoIterator<Item = Pos>, min: Pos, max: Pos) -> String {
+    let mut grid = Grid::with_bounds(min, max, '.');
+    let mut put = |p: Pos, c: char| {
+        if let Some(cell) = grid.get_mut(p) {
+            *cell = c;
+        }
+    };
+
+    visited.into_iter().for_each(|p| put(p, '#'));
+    put(Pos::new(0, 0), 's');
+    for (n, &knot) in knots.iter().enumerate().rev() {
+        let c = match n {
+            0 => 'H',
+            1 if knots.len() == 2 => 'T',
+            n => char::from_digit(n as u32, 10).unwrap(),
+        };
+        put(knot, c);
+    }
+
+    grid.to_string()
+}
+
 #[derive(Debug, Default)]
 struct Data {
-    rope: Vec<(i32, i32)>,
-    t_points: HashSet<(i32, i32)>,
-    vis: (u32, i32),
+    rope: Vec<Point2<i32>>,
+    t_points: HashSet<Point2<i32>>,
 }
 
 impl Data {
@@ -50,10 +189,10 @@ impl Data {
         let mut s = Self { ..Default::default() };
 
         for _ in 0..2 {
-            s.rope.push((0, 0));
+            s.rope.push(Point2::origin());
         }
 
-        s.t_points.insert((0, 0));
+        s.t_points.insert(Point2::origin());
 
         s
     }
@@ -62,35 +201,18 @@ impl Data {
         let mut s = Self { ..Default::default() };
 
         for _ in 0..10 {
-            s.rope.push((0, 0));
+            s.rope.push(Point2::origin());
         }
 
-        s.t_points.insert((0, 0));
+        s.t_points.insert(Point2::origin());
         s
     }
 
     fn apply_move(&mut self, m: Instruction) {
-        use Direction::*;
-
-        if self.vis.0 > 1 { println!("{m:?}"); };
-
         for _ in 0..m.1 {
-            match &m.0 {
-                Right => self.rope[0].1 += 1,
-                Left =>  self.rope[0].1 -= 1,
-                Up =>    self.rope[0].0 += 1,
-                Down =>  self.rope[0].0 -= 1,
-            }
-            // println!("{}, {}", self.rope[0].0, self.rope[0].1);
+            self.rope[0] += m.0.vector();
             self.update_tail();
-            // self.print_rope();
         }
-        // println!("{:?}", self.tail());
-        // for i in 1..self.rope.len() {
-        //     print!("{:?},", Point(self.rope[i-1]) - Point(self.rope[i]));
-        // }
-        // println!("\n");
-        self.visualize();
     }
 
     fn update_tail(&mut self) {
@@ -98,62 +220,59 @@ impl Data {
 
         for i in 1..self.rope.len() {
            if !self.is_tail_connected(i) {
-               let mut new_tail = self.rope[i-1];
+               let head = self.rope[i-1];
 
-               match self.calculate_position(i) {
+               let step = match self.calculate_position(i) {
                    StraightH(s) => {
                        if s > 0 {
                            // right
-                           new_tail.1 -= 1;
+                           Vec2::new(-1, 0)
                        } else {
-                           new_tail.1 += 1;
+                           Vec2::new(1, 0)
                        }
                    },
                    StraightV(s) => {
-                       if s > 0 {
+                       if s < 0 {
                            // up
-                           new_tail.0 -= 1;
+                           Vec2::new(0, 1)
                        } else {
-                           new_tail.0 += 1;
+                           Vec2::new(0, -1)
                        }
                    },
                    KnightH(a) => {
                        if a > 0 {
                            // moved to the right
-                           new_tail.1 -= 1;
+                           Vec2::new(-1, 0)
                        } else {
-                           new_tail.1 += 1;
+                           Vec2::new(1, 0)
                        }
                    },
                    KnightV(b) => {
-                       if b > 0 {
+                       if b < 0 {
                            // moved up
-                           new_tail.0 -= 1;
+                           Vec2::new(0, 1)
                        } else {
-                           new_tail.0 += 1;
+                           Vec2::new(0, -1)
                        }
                    },
                    DiagU(d) => {
-                       if d > 0 {
+                       if d < 0 {
                            // moved right
-                           new_tail.1 -= 1;
-                           new_tail.0 -= 1;
+                           Vec2::new(-1, 1)
                        } else {
-                           new_tail.1 += 1;
-                           new_tail.0 -= 1;
+                           Vec2::new(1, 1)
                        }
                    },
                    DiagD(d) => {
-                       if d > 0 {
+                       if d < 0 {
                            // moved right
-                           new_tail.1 -= 1;
-                           new_tail.0 += 1;
+                           Vec2::new(-1, -1)
                        } else {
-                           new_tail.1 += 1;
-                           new_tail.0 += 1;
+                           Vec2::new(1, -1)
                        }
                    }
-               }
+               };
+               let new_tail = head + step;
 
                self.rope[i] = new_tail;
 
@@ -162,98 +281,61 @@ impl Data {
                }
            }
         }
-        // self.print_rope();
     }
 
     fn is_tail_connected(&self, index: usize) -> bool {
-        let i: usize = index - 1;
-        let x_range = (self.rope[i].0 - 1)..=(self.rope[i].0 + 1);
-        let y_range = (self.rope[i].1 - 1)..=(self.rope[i].1 + 1);
-
-        let t_pos = self.rope[index];
-        x_range
-            .cartesian_product(y_range)
-            .any(|point| point == t_pos)
+        self.rope[index - 1].chebyshev(&self.rope[index]) <= 1
     }
 
     fn calculate_position(&self, index: usize) -> Diff {
         use Diff::*;
 
-        let head = self.rope[index - 1];
-        let tail = self.rope[index];
+        let diff = self.rope[index - 1] - self.rope[index];
 
-        if head.1 == tail.1 {
-            return StraightV(head.0 - tail.0);
-        } else if head.0 == tail.0 {
-            return StraightH(head.1 - tail.1);
+        if diff.x == 0 {
+            return StraightV(diff.y);
+        } else if diff.y == 0 {
+            return StraightH(diff.x);
         }
 
-        if (head.1 - tail.1).abs() == 2 && (head.0 - tail.0).abs() == 2 {
+        if diff.x.abs() == 2 && diff.y.abs() == 2 {
             // diagonal
-            if head.1 > tail.1 {
+            if diff.x > 0 {
                 // up
-                return DiagU(head.0 - tail.0);
+                return DiagU(diff.y);
             } else {
-                return DiagD(head.0 - tail.0);
+                return DiagD(diff.y);
             }
         }
 
         // leftovers must be knight
-        if (head.1 - tail.1).abs() > 1 {
+        if diff.x.abs() > 1 {
             // horizontal
-            KnightH(head.1 - tail.1)
+            KnightH(diff.x)
         } else {
             //vertical
-            KnightV( head.0 - tail.0)
+            KnightV(diff.y)
         }
     }
 
-    #[allow(dead_code)]
-    fn print_rope(&self) {
-        println!("{:?}", self.rope.iter().map(|&i| Point(i)).collect::<Vec<Point>>());
-    }
-
-    #[allow(dead_code)]
-    fn head(&self) -> Point {
-        Point(*self.rope.first().unwrap())
+    #[cfg(test)]
+    fn head(&self) -> Point2<i32> {
+        *self.rope.first().unwrap()
     }
 
-    #[allow(dead_code)]
-    fn tail(&self) -> Point {
-        Point(*self.rope.last().unwrap())
+    #[cfg(test)]
+    fn tail(&self) -> Point2<i32> {
+        *self.rope.last().unwrap()
     }
 
-    #[allow(dead_code)]
-    fn visualize(&self) {
-        if self.vis.0 < 1 {
-            return;
-        }
-        let v = self.vis;
-        let mut grid = vec![vec![".".to_string(); (v.0 + v.1 as u32) as usize]; (v.0 + v.1 as u32) as usize];
-
-        self
-            .rope
-            .iter()
-            .enumerate()
-            .for_each(|(n, (x, y))| {
-                let (x1, y1) = ((*x + v.1) as usize, (*y + v.1) as usize);
-                if grid[x1][y1] == "." {
-                    let k = if n > 0 {n.to_string()} else { "H".to_string() };
-                    grid[x1][y1] = k;
-                }
-            });
-
-        if grid[v.1 as usize][v.1 as usize] == "." {
-            grid[v.1 as usize][v.1 as usize] = "s".to_string();
-        }
-
-        for p in &self.t_points {
-            grid[(p.0 + v.1) as usize][(p.1 + v.1) as usize] = "#".to_string();
-        }
+    #[cfg(test)]
+    fn picture(&self) -> String {
+        let knots: Vec<Pos> = self.rope.iter().map(screen).collect();
+        let all: Vec<Pos> = knots.iter().copied().chain(self.t_points.iter().map(screen)).chain([Pos::new(0, 0)]).collect();
 
-        for v in grid.into_iter().rev() {
-            println!("{}", v.into_iter().collect::<String>());
-        }
+        let min = Pos::new(all.iter().map(|p| p.x).min().unwrap(), all.iter().map(|p| p.y).min().unwrap());
+        let max = Pos::new(all.iter().map(|p| p.x).max().unwrap(), all.iter().map(|p| p.y).max().unwrap());
+        draw(&knots, self.t_points.iter().map(screen), min, max)
     }
 }
 
@@ -269,76 +351,55 @@ enum Diff {
     DiagD(i32),
 }
 
-#[derive(PartialEq, PartialOrd, Ord, Eq)]
-struct Point((i32, i32));
-impl std::fmt::Debug for Point {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.0.0, self.0.1)
-    }
-}
-
-impl std::ops::Sub for Point {
-    type Output = (i32, i32);
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        (self.0.0 - rhs.0.0, self.0.1 - rhs.0.1)
-    }
-}
-
-#[derive(Debug, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-
-}
-
 #[derive(Debug, PartialEq)]
 struct Instruction(Direction, u32);
 
-impl Direction {
-    fn from(d: &str) -> Self {
-        match d {
-            "U" => Self::Up,
-            "D" => Self::Down,
-            "L" => Self::Left,
-            "R" => Self::Right,
-            _ => unimplemented!("Unknown direction! {}", d),
-        }
-    }
-}
-
-fn parse_instruction(i: &str) -> IResult<&str, Instruction> {
-    map(
-        separated_pair(alpha1, tag(" "), character::complete::u32),
-        |(d, n)| Instruction(Direction::from(d), n),
+fn parse_instruction(i: &str) -> IResult<'_, Instruction> {
+    map_opt(
+        separated_pair(anychar, token(" "), character::complete::u32),
+        |(d, n)| Some(Instruction(Direction::from_char(d)?, n)),
     )(i)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::frames::Frames;
+    use proptest::prelude::*;
     use Direction::*;
-    use std::fs;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "13");
-    }
+    aoc_common::examples!(Solver);
 
-    #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "1");
+    proptest! {
+        #[test]
+        fn prop_instruction_roundtrip(d in "[UDLR]", n in any::<u32>()) {
+            let (_, i) = parse_instruction(&format!("{d} {n}")).unwrap();
+            prop_assert_eq!(i, Instruction(Direction::from_char(d.chars().next().unwrap()).unwrap(), n));
+        }
+
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = parse(&source);
+        }
+
+        #[test]
+        fn prop_parsers_agree(moves in prop::collection::vec(("[UDLR]", 1..20usize), 1..20)) {
+            let source = moves.iter().map(|(d, n)| format!("{d} {n}")).collect::<Vec<_>>().join("\n");
+            let (rest, steps) = other::moves(&source).unwrap();
+
+            prop_assert_eq!(rest, "");
+            prop_assert_eq!(steps.len(), parse(&source).unwrap().iter().map(|i| i.1 as usize).sum::<usize>());
+        }
     }
 
     #[test]
-    // #[ignore]
-    fn test_part2_longer() {
-        let source = fs::read_to_string("./test_input2.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "36");
+    fn test_parse_error() {
+        let e = part1("R 4\nU x\nL 3\n").unwrap_err();
+        assert_eq!(e.to_string(), "Expected a number at line 2, column 3");
+
+        let e = part2("R 4\nU x\nL 3\n").unwrap_err();
+        assert_eq!(e.to_string(), "Expected a number at line 2, column 3");
+        assert!(part2("xyz 1\n1,2\n").is_err());
     }
 
     #[test]
@@ -350,75 +411,75 @@ mod tests {
     fn do_test_movement(d: &mut Data, dd: Direction, n: u32, head_pos: (i32, i32), tail_pos: (i32, i32)) {
         d.apply_move(Instruction(dd, n));
 
-        assert_eq!(d.head(), Point(head_pos));
-        assert_eq!(d.tail(), Point(tail_pos));
+        assert_eq!(d.head(), Point2::from(head_pos));
+        assert_eq!(d.tail(), Point2::from(tail_pos));
     }
 
     #[test]
     fn test_movement() {
         let mut d = Data::new();
-        do_test_movement(&mut d, Up, 2, (2, 0), (1, 0));
-        do_test_movement(&mut d, Up, 1, (3, 0), (2, 0));
+        do_test_movement(&mut d, Up, 2, (0, -2), (0, -1));
+        do_test_movement(&mut d, Up, 1, (0, -3), (0, -2));
 
         let mut d = Data::new();
-        do_test_movement(&mut d, Right, 2, (0, 2), (0, 1));
-        do_test_movement(&mut d, Up, 1, (1, 2), (0, 1));
-        do_test_movement(&mut d, Up, 1, (2, 2), (1, 2));
+        do_test_movement(&mut d, Right, 2, (2, 0), (1, 0));
+        do_test_movement(&mut d, Up, 1, (2, -1), (1, 0));
+        do_test_movement(&mut d, Up, 1, (2, -2), (2, -1));
 
         let mut d = Data::new();
-        do_test_movement(&mut d, Right, 2, (0, 2), (0, 1));
-        do_test_movement(&mut d, Up, 1, (1, 2), (0, 1));
-        do_test_movement(&mut d, Right, 1, (1, 3), (1, 2));
+        do_test_movement(&mut d, Right, 2, (2, 0), (1, 0));
+        do_test_movement(&mut d, Up, 1, (2, -1), (1, 0));
+        do_test_movement(&mut d, Right, 1, (3, -1), (2, -1));
     }
 
     #[test]
     fn test_diff() {
         let mut d = Data::new();
-        d.rope[0] = (0, 2);
+        d.rope[0] = Point2::new(2, 0);
         assert_eq!(d.calculate_position(1), Diff::StraightH(2));
 
-        d.rope[0] = (0, -2);
+        d.rope[0] = Point2::new(-2, 0);
         assert_eq!(d.calculate_position(1), Diff::StraightH(-2));
 
-        d.rope[0] = (2, 0);
-        assert_eq!(d.calculate_position(1), Diff::StraightV(2));
-
-        d.rope[0] = (-2, 0);
+        d.rope[0] = Point2::new(0, -2);
         assert_eq!(d.calculate_position(1), Diff::StraightV(-2));
 
-        d.rope[0] = (1, 2);
-        assert_eq!(d.calculate_position(1), Diff::KnightH(2));
+        d.rope[0] = Point2::new(0, 2);
+        assert_eq!(d.calculate_position(1), Diff::StraightV(2));
 
-        d.rope[0] = (-1, 2);
+        d.rope[0] = Point2::new(2, -1);
         assert_eq!(d.calculate_position(1), Diff::KnightH(2));
 
-        d.rope[0] = (2, 1);
-        assert_eq!(d.calculate_position(1), Diff::KnightV(2));
+        d.rope[0] = Point2::new(2, 1);
+        assert_eq!(d.calculate_position(1), Diff::KnightH(2));
 
-        d.rope[0] = (-2, -1);
+        d.rope[0] = Point2::new(1, -2);
         assert_eq!(d.calculate_position(1), Diff::KnightV(-2));
 
-        d.rope[0] = (-2, -2);
-        assert_eq!(d.calculate_position(1), Diff::DiagD(-2));
+        d.rope[0] = Point2::new(-1, 2);
+        assert_eq!(d.calculate_position(1), Diff::KnightV(2));
 
-        d.rope[0] = (-2, -1);
-        assert_eq!(d.calculate_position(1), Diff::KnightV(-2));
+        d.rope[0] = Point2::new(-2, 2);
+        assert_eq!(d.calculate_position(1), Diff::DiagD(2));
+
+        d.rope[0] = Point2::new(-1, 2);
+        assert_eq!(d.calculate_position(1), Diff::KnightV(2));
     }
 
     #[test]
     fn test_moves_smaller() {
         let mut d = Data::longer();
-        do_test_movement(&mut d, Right, 4, (0, 4), (0, 0));
-        do_test_movement(&mut d, Up, 1, (1, 4), (0, 0));
-        assert_eq!(d.rope[1], (0, 3));
+        do_test_movement(&mut d, Right, 4, (4, 0), (0, 0));
+        do_test_movement(&mut d, Up, 1, (4, -1), (0, 0));
+        assert_eq!(d.rope[1], Point2::new(3, 0));
 
-        do_test_movement(&mut d, Up, 1, (2, 4), (0, 0));
-        assert_eq!(d.rope[1], (1, 4));
-        assert_eq!(d.rope[2], (1, 3));
+        do_test_movement(&mut d, Up, 1, (4, -2), (0, 0));
+        assert_eq!(d.rope[1], Point2::new(4, -1));
+        assert_eq!(d.rope[2], Point2::new(3, -1));
 
-        do_test_movement(&mut d, Up, 2, (4, 4), (0, 0));
-        assert_eq!(d.rope[2], (2, 4));
-        assert_eq!(d.rope[4], (2, 2));
+        do_test_movement(&mut d, Up, 2, (4, -4), (0, 0));
+        assert_eq!(d.rope[2], Point2::new(4, -2));
+        assert_eq!(d.rope[4], Point2::new(2, -2));
     }
 
     #[test]
@@ -426,31 +487,30 @@ mod tests {
     fn test_visualization() {
         let mut d = Data::longer();
         d.apply_move(Instruction(Right, 4));
-        d.vis = (6, 0);
         d.apply_move(Instruction(Up, 3));
         d.apply_move(Instruction(Up, 1));
+        print!("{}", d.picture());
 
-        assert!(false);
+        panic!("Check the visualization output!");
     }
 
     #[test]
     #[ignore]
     fn test_moves_longer() {
         let mut d = Data::longer();
-        d.vis = (25, 11);
-        do_test_movement(&mut d, Right, 5, (0, 5), (0, 0));
+        do_test_movement(&mut d, Right, 5, (5, 0), (0, 0));
 
         d.apply_move(Instruction(Up, 8));
-        assert_eq!(d.rope[0], (8, 5));
-        assert_eq!(d.rope[1], (7, 5));
-        assert_eq!(d.rope[2], (6, 5));
-        assert_eq!(d.rope[3], (5, 5));
-        assert_eq!(d.rope[4], (4, 5));
-        assert_eq!(d.rope[5], (4, 4));
-        assert_eq!(d.rope[6], (3, 3));
-        assert_eq!(d.rope[7], (2, 2));
-        assert_eq!(d.rope[8], (1, 1));
-        assert_eq!(d.rope[9], (0, 0));
+        assert_eq!(d.rope[0], Point2::new(5, -8));
+        assert_eq!(d.rope[1], Point2::new(5, -7));
+        assert_eq!(d.rope[2], Point2::new(5, -6));
+        assert_eq!(d.rope[3], Point2::new(5, -5));
+        assert_eq!(d.rope[4], Point2::new(5, -4));
+        assert_eq!(d.rope[5], Point2::new(4, -4));
+        assert_eq!(d.rope[6], Point2::new(3, -3));
+        assert_eq!(d.rope[7], Point2::new(2, -2));
+        assert_eq!(d.rope[8], Point2::new(1, -1));
+        assert_eq!(d.rope[9], Point2::new(0, 0));
         // do_test_movement(&mut d, Up, 1, (4, 2), (0, 0));
 
         d.apply_move(Instruction(Left, 8));
@@ -459,7 +519,43 @@ mod tests {
         d.apply_move(Instruction(Down, 10));
         d.apply_move(Instruction(Left, 25));
         d.apply_move(Instruction(Up, 20));
-        assert!(false);
+        print!("{}", d.picture());
+        panic!("Check the visualization output!");
+    }
+
+    #[test]
+    fn test_watch() {
+        let mut frames = Frames::default();
+        let answer = Solver.visualize(2, include_str!("../test_input2.txt"), &mut frames).unwrap().unwrap();
+        assert_eq!(answer, Answer::Number(36));
+        assert_eq!(frames.0.len(), 8);
+        assert_eq!(frames.0[7].caption, "U 20");
+        assert_eq!((frames.0[7].width(), frames.0[7].height()), (60, 30));
+
+        let mut frames = Frames::default();
+        Solver.visualize(1, include_str!("../test_input.txt"), &mut frames).unwrap().unwrap();
+        let picture = frames.0[0].to_string();
+        assert!(picture.contains("s##TH"), "{picture}");
+
+        assert!(Solver.visualize(3, include_str!("../test_input.txt"), &mut frames).is_none());
+    }
+
+    #[test]
+    fn test_compare_example() {
+        let source = include_str!("../test_input2.txt");
+        assert_eq!(compare_part2(source).unwrap(), None);
     }
 
+    #[test]
+    #[ignore = "my rope is still wrong, this reports where"]
+    fn test_compare_generated() {
+        use aoc_common::rand::{rngs::StdRng, SeedableRng};
+
+        for seed in 0..20 {
+            let source = generate::input(&mut StdRng::seed_from_u64(seed), 200);
+            if let Some(d) = compare_part2(&source).unwrap() {
+                panic!("seed {seed}: {d}");
+            }
+        }
+    }
 }
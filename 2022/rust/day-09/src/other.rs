@@ -1,95 +1,85 @@
-/// This solution is borrowed from https://github.com/scristobal/advent-of-code/blob/main/day-09/src/lib.rs
-/// I will have to compare outputs step by step later to see where mine is wrong.
-/// All tests pass and the visualization matches the example .. yet the result is wrong.
+//! This solution is borrowed from https://github.com/scristobal/advent-of-code/blob/main/day-09/src/lib.rs
+//! `compare_part2` compares the outputs step by step to see where mine is wrong.
+//! All tests pass and the visualization matches the example .. yet the result is wrong.
 
 use std::collections::HashSet;
-use std::fmt::{self, Debug};
+
+use aoc_common::geometry::{Direction, Point2, Vec2};
+use aoc_common::parse::{finish, token, IResult};
+use aoc_common::Result;
 
 use nom::branch::alt;
 use nom::character::complete;
 use nom::character::complete::newline;
-use nom::sequence::separated_pair;
-use nom::{bytes::complete::tag, multi::separated_list0, IResult};
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct Coords([i32; 2]);
-
-impl Debug for Coords {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Coord")
-            .field("x", &self.0[0])
-            .field("y", &self.0[1])
-            .finish()
-    }
-}
+use nom::combinator::{all_consuming, cut, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{separated_pair, terminated};
 
-impl Coords {
-    fn mov(&mut self, dir: &Coords) {
-        self.0[0] += dir.0[0];
-        self.0[1] += dir.0[1];
-    }
+type Coords = Point2<i32>;
 
-    fn diff(&self, coord: &Coords) -> Coords {
-        Coords([self.0[0] - coord.0[0], self.0[1] - coord.0[1]])
-    }
-
-    fn normalize(&mut self) {
-        if self.0[0].abs() <= 1 && self.0[1].abs() <= 1 {
-            *self = Coords([0, 0]);
-        }
-        if self.0[0].abs() > 1 {
-            self.0[0] /= self.0[0].abs();
-        }
-        if self.0[1].abs() > 1 {
-            self.0[1] /= self.0[1].abs();
-        }
+/// The step a knot takes towards the previous one, `diff` away, once they stop touching.
+fn normalize(diff: Vec2<i32>) -> Vec2<i32> {
+    if diff.chebyshev_len() <= 1 {
+        return Vec2::default();
     }
+    diff.signum()
 }
 
-fn up(input: &str) -> IResult<&str, Vec<Coords>> {
-    let (input, (_, times)) = separated_pair(tag("U"), tag(" "), complete::i32)(input)?;
-    Ok((input, (0..times).map(|_| Coords([0, 1])).collect()))
+fn up(input: &str) -> IResult<'_, Vec<Vec2<i32>>> {
+    let (input, (_, times)) = separated_pair(token("U"), cut(token(" ")), cut(complete::i32))(input)?;
+    Ok((input, (0..times).map(|_| Direction::Up.vector()).collect()))
 }
 
-fn down(input: &str) -> IResult<&str, Vec<Coords>> {
-    let (input, (_, times)) = separated_pair(tag("D"), tag(" "), complete::i32)(input)?;
-    Ok((input, (0..times).map(|_| Coords([0, -1])).collect()))
+fn down(input: &str) -> IResult<'_, Vec<Vec2<i32>>> {
+    let (input, (_, times)) = separated_pair(token("D"), cut(token(" ")), cut(complete::i32))(input)?;
+    Ok((input, (0..times).map(|_| Direction::Down.vector()).collect()))
 }
 
-fn left(input: &str) -> IResult<&str, Vec<Coords>> {
-    let (input, (_, times)) = separated_pair(tag("L"), tag(" "), complete::i32)(input)?;
-    Ok((input, (0..times).map(|_| Coords([-1, 0])).collect()))
+fn left(input: &str) -> IResult<'_, Vec<Vec2<i32>>> {
+    let (input, (_, times)) = separated_pair(token("L"), cut(token(" ")), cut(complete::i32))(input)?;
+    Ok((input, (0..times).map(|_| Direction::Left.vector()).collect()))
 }
 
-fn right(input: &str) -> IResult<&str, Vec<Coords>> {
-    let (input, (_, times)) = separated_pair(tag("R"), tag(" "), complete::i32)(input)?;
-    Ok((input, (0..times).map(|_| Coords([1, 0])).collect()))
+fn right(input: &str) -> IResult<'_, Vec<Vec2<i32>>> {
+    let (input, (_, times)) = separated_pair(token("R"), cut(token(" ")), cut(complete::i32))(input)?;
+    Ok((input, (0..times).map(|_| Direction::Right.vector()).collect()))
 }
 
-fn moves(input: &str) -> IResult<&str, Vec<Coords>> {
-    let (input, moves) = separated_list0(newline, alt((up, down, right, left)))(input)?;
+pub(crate) fn moves(input: &str) -> IResult<'_, Vec<Vec2<i32>>> {
+    let (input, moves) = all_consuming(terminated(
+        separated_list1(newline, alt((up, down, right, left))),
+        opt(newline),
+    ))(input)?;
     Ok((input, moves.into_iter().flatten().collect()))
 }
 
-struct LongRope([Coords; 10]);
+pub(crate) struct Rope(pub(crate) Vec<Coords>);
 
-pub fn solve_part2(input: &str) -> String {
-    let (_, moves) = moves(input).unwrap();
+impl Rope {
+    pub(crate) fn new(knots: usize) -> Self {
+        Rope(vec![Coords::origin(); knots])
+    }
 
-    moves
-        .into_iter()
-        .scan(LongRope([Coords([0, 0]); 10]), |state, mov| {
-            state.0[0].mov(&mov);
+    /// Moves the head by `mov`, a single step, and returns where the tail ends up.
+    pub(crate) fn step(&mut self, mov: Vec2<i32>) -> Coords {
+        self.0[0] += mov;
+
+        for l in 0..(self.0.len() - 1) {
+            let diff = self.0[l] - self.0[l + 1];
+            self.0[l + 1] += normalize(diff);
+        }
 
-            for l in 0..(state.0.len() - 1) {
-                let mut diff = state.0[l].diff(&state.0[l + 1]);
-                diff.normalize();
-                state.0[l + 1].mov(&diff);
-            }
+        self.0[self.0.len() - 1]
+    }
+}
 
-            Some(state.0[9])
-        })
+pub fn solve_part2(input: &str) -> Result<String> {
+    let moves = finish(input, moves(input))?;
+
+    Ok(moves
+        .into_iter()
+        .scan(Rope::new(10), |state, mov| Some(state.step(mov)))
         .collect::<HashSet<_>>()
         .len()
-        .to_string()
+        .to_string())
 }
@@ -1,4 +1,4 @@
-use advent_of_code_2022::part2;
+use aoc_2022_day_10::part2;
 use color_eyre::Result;
 use std::fs;
 
@@ -1,7 +1,15 @@
-use std::collections::BTreeMap;
+use aoc_common::{
+    parse::{finish, token, IResult},
+    Answer, Frame, Recorder, Solution,
+};
+use std::{collections::BTreeMap, hint::black_box};
 
 use color_eyre::Result;
-use nom::{branch::alt, bytes::complete::tag, combinator::map, sequence::separated_pair, IResult};
+use nom::{
+    branch::alt,
+    combinator::{all_consuming, map},
+    sequence::separated_pair,
+};
 
 pub fn part1(source: &str) -> Result<String> {
     let mut scores: BTreeMap<i32, i32> = BTreeMap::new();
@@ -17,12 +25,17 @@ pub fn part1(source: &str) -> Result<String> {
             // dbg!(2, &register, &counter, register * (counter+2));
             scores.insert(counter + 2, (counter + 2) * register);
         }
-    });
+    })?;
 
     Ok(scores.values().sum::<i32>().to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
+    crt(source, None)
+}
+
+/// Draws the CRT pixel by pixel, showing the screen after every cycle.
+fn crt(source: &str, mut recorder: Option<&mut dyn Recorder>) -> Result<String> {
     let mut res = "".to_string();
 
     process(source, |counter, register| {
@@ -36,24 +49,56 @@ pub fn part2(source: &str) -> Result<String> {
         } else {
             res += ".";
         }
-    });
+
+        if let Some(r) = recorder.as_deref_mut() {
+            r.record(
+                Frame::new(&res).with_caption(format!("cycle {}, X = {register}", counter + 1)),
+            );
+        }
+    })?;
 
     Ok(res)
 }
 
-fn process(input: &str, mut actor: impl FnMut(i32, i32)) {
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse(input).map(|instructions| {
+            black_box(instructions);
+        }))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn visualize(
+        &self,
+        part: u8,
+        input: &str,
+        recorder: &mut dyn Recorder,
+    ) -> Option<Result<Answer>> {
+        (part == 2).then(|| Ok(crt(input, Some(recorder))?.into()))
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Instruction>> {
+    input
+        .lines()
+        .map(|l| finish(input, all_consuming(parse_instruction)(l)))
+        .collect()
+}
+
+fn process(input: &str, mut actor: impl FnMut(i32, i32)) -> Result<()> {
     let mut counter = 0;
     let mut register = 1;
 
-    for (_cc, curr_line) in input.lines().enumerate() {
-        let ins = parse_instruction(curr_line);
-        let i;
-        if let Ok(ii) = ins {
-            i = ii.1;
-        } else {
-            break;
-        }
-
+    for i in parse(input)? {
         // println!("{_cc}: ({counter}) => {:?}", &i);
         actor(counter, register);
 
@@ -68,6 +113,8 @@ fn process(input: &str, mut actor: impl FnMut(i32, i32)) {
             }
         }
     }
+
+    Ok(())
 }
 
 #[derive(Debug, PartialEq)]
@@ -76,40 +123,61 @@ enum Instruction {
     Addx(i32),
 }
 
-fn parse_noop(i: &str) -> IResult<&str, Instruction> {
-    map(tag("noop"), |_| Instruction::Noop)(i)
+fn parse_noop(i: &str) -> IResult<'_, Instruction> {
+    map(token("noop"), |_| Instruction::Noop)(i)
 }
 
-fn parse_addrx(i: &str) -> IResult<&str, Instruction> {
+fn parse_addrx(i: &str) -> IResult<'_, Instruction> {
     map(
-        separated_pair(tag("addx"), tag(" "), nom::character::complete::i32),
+        separated_pair(token("addx"), token(" "), nom::character::complete::i32),
         |(_, q)| Instruction::Addx(q),
     )(i)
 }
 
-fn parse_instruction(i: &str) -> IResult<&str, Instruction> {
+fn parse_instruction(i: &str) -> IResult<'_, Instruction> {
     alt((parse_addrx, parse_noop))(i)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use aoc_common::frames::Frames;
+    use proptest::prelude::*;
+
+    aoc_common::examples!(Solver);
+
+    proptest! {
+        #[test]
+        fn prop_parse_roundtrip(adds in prop::collection::vec(prop::option::of(any::<i32>()), 0..30)) {
+            let source = adds
+                .iter()
+                .map(|a| a.map_or("noop".to_string(), |n| format!("addx {n}")))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let expected: Vec<_> = adds.iter().map(|a| a.map_or(Instruction::Noop, Instruction::Addx)).collect();
+
+            prop_assert_eq!(parse(&source).unwrap(), expected);
+        }
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "13140");
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = parse(&source);
+        }
     }
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        let output = fs::read_to_string("./test2_output.txt").unwrap();
-        let result = part2(&source).unwrap();
-        println!("{}", &result);
-        println!("{}", &output);
-        assert_eq!(result + "\n", output);
+    fn test_visualize() {
+        let source = include_str!("../test_input.txt");
+        let mut frames = Frames::default();
+        let answer = Solver.visualize(2, source, &mut frames).unwrap().unwrap();
+
+        assert_eq!(frames.0.len(), 240);
+        assert_eq!(frames.0[1].lines, vec!["##"]);
+        assert_eq!(
+            frames.0[239].to_string(),
+            format!("{answer}\ncycle 240, X = 17\n")
+        );
+        assert!(Solver.visualize(1, source, &mut frames).is_none());
     }
 
     #[test]
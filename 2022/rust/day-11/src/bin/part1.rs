@@ -1,9 +1,10 @@
-use advent_of_code_2022::part1;
+use aoc_2022_day_11::part1;
 use color_eyre::Result;
 use std::fs;
 
 fn main() -> Result<()> {
     color_eyre::install().unwrap();
+    aoc_common::log::init(0);
 
     let file = fs::read_to_string("./real_input.txt").unwrap();
     println!("{}", part1(&file)?);
@@ -0,0 +1,132 @@
+//! Random monkey descriptions, `size` is the number of items they start with, shared among two to eight monkeys.
+//!
+//! Like the real inputs, every test is a different prime and at most one monkey squares the worry level.
+//! Part 1 never takes the worry modulo anything, so layouts that would overflow it are thrown away.
+
+use std::{collections::VecDeque, fmt::Write};
+
+use aoc_common::rand::{seq::SliceRandom, Rng};
+
+use crate::{Monkey, Op};
+
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    let mut attempts = 0;
+    let monkeys = loop {
+        attempts += 1;
+        // give up on squaring when it keeps overflowing
+        let monkeys = monkeys(rng, size, attempts < 50);
+        if fits(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    let mut res = String::new();
+    for (i, m) in monkeys.iter().enumerate() {
+        if i > 0 {
+            res.push('\n');
+        }
+        write!(res, "Monkey {i}:\n{m}").unwrap();
+    }
+    res
+}
+
+fn monkeys(rng: &mut impl Rng, size: usize, squaring: bool) -> Vec<Monkey> {
+    let count = rng.gen_range(2..=8).min(size.max(2));
+    let mut primes = PRIMES.to_vec();
+    primes.shuffle(rng);
+    let square = squaring.then(|| rng.gen_range(0..count));
+
+    let mut monkeys: Vec<Monkey> = (0..count)
+        .map(|i| {
+            let op = match (square == Some(i), rng.gen_bool(0.5)) {
+                (true, _) => Op::Pow,
+                (false, true) => Op::Add(rng.gen_range(1..=8)),
+                (false, false) => Op::Mul(rng.gen_range(2..=19)),
+            };
+
+            let others: Vec<usize> = (0..count).filter(|&o| o != i).collect();
+            let yes = *others.choose(rng).unwrap();
+            let no = *others
+                .iter()
+                .filter(|&&o| o != yes || count == 2)
+                .collect::<Vec<_>>()
+                .choose(rng)
+                .unwrap();
+
+            Monkey {
+                op,
+                test_num: primes[i],
+                test_true_false: (yes, *no),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    // everyone starts with at least one item
+    for i in 0..size.max(count) {
+        let m = if i < count {
+            i
+        } else {
+            rng.gen_range(0..count)
+        };
+        monkeys[m].items.push_back(rng.gen_range(50..100));
+    }
+
+    monkeys
+}
+
+/// Whether the 20 rounds of part 1 stay within a `u64`.
+fn fits(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<VecDeque<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+
+    for _ in 0..20 {
+        for (i, m) in monkeys.iter().enumerate() {
+            while let Some(item) = items[i].pop_front() {
+                let worry = match m.op {
+                    Op::Pow => item.checked_mul(item),
+                    Op::Add(n) => item.checked_add(n),
+                    Op::Mul(n) => item.checked_mul(n),
+                };
+                let Some(worry) = worry.map(|w| w / 3) else {
+                    return false;
+                };
+
+                items[m.throw_to(m.test(worry))].push_back(worry);
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2};
+    use aoc_common::rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..20 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 30);
+            let monkeys = parse(&source).unwrap();
+            assert_eq!(monkeys.iter().map(|m| m.items.len()).sum::<usize>(), 30);
+
+            let tests: HashSet<u64> = monkeys.iter().map(|m| m.test_num).collect();
+            assert_eq!(tests.len(), monkeys.len());
+            assert!(tests.iter().all(|t| PRIMES.contains(t)));
+            assert!(monkeys.iter().filter(|m| m.op == Op::Pow).count() <= 1);
+            for (i, m) in monkeys.iter().enumerate() {
+                let (yes, no) = m.test_true_false;
+                assert!(yes != i && no != i && yes < monkeys.len() && no < monkeys.len());
+            }
+
+            assert_ne!(part1(&source).unwrap(), "0");
+        }
+
+        part2(&input(&mut StdRng::seed_from_u64(0), 10)).unwrap();
+    }
+}
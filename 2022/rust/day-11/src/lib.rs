@@ -1,18 +1,26 @@
+pub mod generate;
 mod parser;
 
+use aoc_common::{
+    parse::finish,
+    rand::{rngs::StdRng, SeedableRng},
+    tracing::{debug, trace},
+    Answer, Solution,
+};
 use color_eyre::Result;
 use itertools::Itertools;
 use parser::*;
-use std::collections::VecDeque;
+use std::{collections::VecDeque, hint::black_box};
+
+fn parse(source: &str) -> Result<Vec<Monkey>> {
+    source.split("\n\n").map(|m| finish(source, parse_monkey_info(m))).collect()
+}
 
 pub fn part1(source: &str) -> Result<String> {
-    let mut monkeys = source
-        .split("\n\n")
-        .map(|m| parse_monkey_info(m).unwrap().1)
-        .collect::<Vec<Monkey>>();
+    let mut monkeys = parse(source)?;
 
     for (i, m) in monkeys.iter().enumerate() {
-        println!("Monkey {i}:\n{m}");
+        trace!("Monkey {i}:\n{m}");
     }
 
     for _round in 0..20 {
@@ -40,9 +48,7 @@ pub fn part1(source: &str) -> Result<String> {
             }
         }
 
-        println!("Round {_round}:");
-        Monkey::list(&monkeys);
-        println!();
+        Monkey::list(&monkeys, _round);
     }
 
     let res = monkeys
@@ -57,10 +63,7 @@ pub fn part1(source: &str) -> Result<String> {
 }
 
 pub fn part2(source: &str) -> Result<String> {
-    let mut monkeys = source
-        .split("\n\n")
-        .map(|m| parse_monkey_info(m).unwrap().1)
-        .collect::<Vec<Monkey>>();
+    let mut monkeys = parse(source)?;
 
     // there is no common divisor because all the numbers are prime
     // try with the product of all of them instead
@@ -86,9 +89,7 @@ pub fn part2(source: &str) -> Result<String> {
         }
 
         if _round % 1000 == 0 {
-            println!("Round {_round}:");
-            Monkey::list(&monkeys);
-            println!();
+            Monkey::list(&monkeys, _round);
         }
     }
 
@@ -103,7 +104,29 @@ pub fn part2(source: &str) -> Result<String> {
     Ok(res.to_string())
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse(input).map(|monkeys| {
+            black_box(monkeys);
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Op {
     Add(u64),
     Mul(u64),
@@ -121,9 +144,9 @@ pub struct Monkey {
 }
 
 impl Monkey {
-    fn list(monkeys: &[Self]) {
+    fn list(monkeys: &[Self], round: usize) {
         for (i, m) in monkeys.iter().enumerate() {
-            println!("Monkey: {i}: {}", m.items.iter().join(", "));
+            debug!(round, "Monkey {i}: {}", m.items.iter().join(", "));
         }
     }
 
@@ -137,7 +160,7 @@ impl Monkey {
     }
 
     fn test(&self, worry: u64) -> bool {
-        worry % self.test_num == 0
+        worry.is_multiple_of(self.test_num)
     }
 
     fn throw_to(&self, test: bool) -> usize {
@@ -170,17 +193,41 @@ impl std::fmt::Display for Monkey {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use std::fs;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "10605");
+    aoc_common::examples!(Solver);
+
+    fn any_monkey() -> impl Strategy<Value = Monkey> {
+        let op = prop_oneof![any::<u64>().prop_map(Op::Add), any::<u64>().prop_map(Op::Mul), Just(Op::Pow)];
+        (prop::collection::vec_deque(any::<u64>(), 1..10), op, any::<u64>(), (0..100usize, 0..100usize)).prop_map(
+            |(items, op, test_num, test_true_false)| Monkey {
+                items,
+                op,
+                test_num,
+                test_true_false,
+                ..Default::default()
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_roundtrip(monkeys in prop::collection::vec(any_monkey(), 1..5)) {
+            let source = monkeys.iter().enumerate().map(|(i, m)| format!("Monkey {i}:\n{m}")).join("\n");
+            prop_assert_eq!(parse(&source).unwrap(), monkeys);
+        }
+
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = parse(&source);
+        }
     }
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "2713310158");
+    fn test_parse_error() {
+        let source = fs::read_to_string("./test_input.txt").unwrap().replacen("old + 6", "old + x", 1);
+        let e = part1(&source).unwrap_err();
+        assert_eq!(e.to_string(), "Expected a number at line 10, column 26");
     }
 }
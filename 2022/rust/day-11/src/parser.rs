@@ -1,16 +1,16 @@
 use super::*;
+use aoc_common::parse::{token, IResult};
 use nom::{
-    bytes::complete::{tag, take, take_till, take_until1},
+    bytes::complete::{take, take_till, take_until1},
     character::{
         self,
         complete::{one_of, space1},
     },
     multi::separated_list1,
     sequence::{pair, preceded, separated_pair},
-    IResult,
 };
 
-fn parse_operation(i: &str) -> IResult<&str, Op> {
+fn parse_operation(i: &str) -> IResult<'_, Op> {
     let (i, val) = take_until1("\n")(i)?;
 
     if val == "old * old" {
@@ -18,7 +18,7 @@ fn parse_operation(i: &str) -> IResult<&str, Op> {
     }
 
     let (_, (op, num)) = preceded(
-        tag("old "),
+        token("old "),
         separated_pair(one_of("+*"), space1, character::complete::u64),
     )(val)?;
 
@@ -31,11 +31,11 @@ fn parse_operation(i: &str) -> IResult<&str, Op> {
     Ok((i, r))
 }
 
-pub fn parse_monkey_info(i: &str) -> IResult<&str, Monkey> {
-    let (i, _) = pair(take_until1("S"), tag("Starting items: "))(i)?; // skip to the interesting part
+pub fn parse_monkey_info(i: &str) -> IResult<'_, Monkey> {
+    let (i, _) = pair(take_until1("S"), token("Starting items: "))(i)?; // skip to the interesting part
 
     let (i, nums) = take_until1("\n")(i)?;
-    let (_, items) = separated_list1(tag(", "), character::complete::u64)(nums)?;
+    let (_, items) = separated_list1(token(", "), character::complete::u64)(nums)?;
 
     let (i, _) = pair(take_until1("="), take(2u8))(i)?; // '=' from "new =" and "= " itself
 
@@ -70,9 +70,9 @@ mod tests {
 
     use super::*;
 
-    fn test_wrap(i: &str) -> IResult<&str, (char, u64)> {
+    fn test_wrap(i: &str) -> IResult<'_, (char, u64)> {
         preceded(
-            tag("old "),
+            token("old "),
             separated_pair(one_of("+*"), space1, character::complete::u64),
         )(i)
     }
@@ -85,7 +85,7 @@ mod tests {
         assert_eq!(r, ('*', 1));
     }
 
-    fn test_wrap2(i: &str) -> IResult<&str, Vec<u32>> {
+    fn test_wrap2(i: &str) -> IResult<'_, Vec<u32>> {
         // i tried and failed, but there must be a better way
         nom::error::context(
             "How to parse this all at once?",
@@ -1,162 +1,173 @@
+use aoc_common::{
+    eyre::eyre,
+    tracing::{debug, enabled, Level},
+    Answer, Frame, Recorder, Solution,
+};
+use aoc_grid::{Grid, Pos};
+use std::hint::black_box;
 use color_eyre::Result;
 use pathfinding::prelude::astar;
 
 pub fn part1(source: &str) -> Result<String> {
-    let (grid, start, end) = parse_grid(source);
-
-    let res = solve(&grid, &start, &end);
-
-    let (path, steps) = res.unwrap();
+    let (grid, path, steps) = shortest(source, 1)?;
     show(path, &grid);
     Ok(steps.to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
-    let (grid, _, end) = parse_grid(source);
-
-    let res = grid
-        .iter()
-        .enumerate()
-        .flat_map(|(y, l)| {
-            l.iter()
-                .enumerate()
-                .filter(|(_, v)| *v == &1u16)
-                .map(|(x, _)| solve(&grid, &Pos(x, y), &end))
-                .collect::<Vec<Option<(Vec<Pos>, u32)>>>()
-        })
-        .flatten()
-        .min_by_key(|(_, steps)| *steps)
-        .unwrap();
-
-    let (path, steps) = res;
+    let (grid, path, steps) = shortest(source, 2)?;
     show(path, &grid);
     Ok(steps.to_string())
 }
 
-fn solve(grid: &[Vec<u16>], start: &Pos, end: &Pos) -> Option<(Vec<Pos>, u32)> {
+/// The shortest path to the end, from the start in part 1 and from the best of the lowest squares in part 2.
+fn shortest(source: &str, part: u8) -> Result<(Grid<u16>, Vec<Pos>, u32)> {
+    let (grid, start, end) = parse_grid(source)?;
+
+    let res = if part == 1 {
+        solve(&grid, &start, &end)
+    } else {
+        grid
+            .iter()
+            .filter(|(_, v)| **v == 1u16)
+            .flat_map(|(p, _)| solve(&grid, &p, &end))
+            .min_by_key(|(_, steps)| *steps)
+    };
+
+    let (path, steps) = res.ok_or_else(|| eyre!("There is no way to the end!"))?;
+    Ok((grid, path, steps))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse_grid(input).map(|g| {
+            black_box(g);
+        }))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn visualize(&self, part: u8, input: &str, recorder: &mut dyn Recorder) -> Option<Result<Answer>> {
+        matches!(part, 1 | 2).then(|| shortest(input, part).and_then(|(_, path, steps)| {
+            walk(input, &path, recorder)?;
+            Ok(steps.into())
+        }))
+    }
+}
+
+fn solve(grid: &Grid<u16>, start: &Pos, end: &Pos) -> Option<(Vec<Pos>, u32)> {
     astar(
         start,
-        |p| p.successors(grid),
-        |p| p.distance(end),
+        |p| successors(p, grid),
+        |p| p.manhattan(end) as u32,
         |p| p == end,
     )
 }
 
-fn show(path: Vec<Pos>, grid: &[Vec<u16>]) {
-    for y in 0..grid.len() {
-        for x in 0..grid[0].len() {
-            if path.contains(&Pos(x, y)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
+fn show(path: Vec<Pos>, grid: &Grid<u16>) {
+    if !enabled!(Level::DEBUG) {
+        return;
     }
-}
 
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Pos(usize, usize);
-
-impl Pos {
-    fn distance(&self, other: &Pos) -> u32 {
-        (self.0.abs_diff(other.0) + self.1.abs_diff(other.1)) as u32
+    let mut map = Grid::new(grid.width(), grid.height(), '.');
+    for p in path {
+        map[p] = '#';
     }
 
-    fn successors(&self, grid: &[Vec<u16>]) -> Vec<(Pos, u32)> {
-        let &Pos(x, y) = self;
-        let val = grid[y][x];
-
-        self.neighbors(x, y, grid)
-            .into_iter()
-            .filter(|(ix, iy)| Pos(*ix, *iy) != *self)
-            .filter(|(ix, iy)| {
-                grid[*iy][*ix] <= (val + 1)
-            })
-            .map(|(ix, iy)| (Pos(ix, iy), 1))
-            .collect()
+    debug!("the shortest path:\n{map}");
+}
+
+/// The height map with the path drawn over it the way the puzzle does, one more arrow every frame.
+fn walk(source: &str, path: &[Pos], recorder: &mut dyn Recorder) -> Result<()> {
+    let mut map = Grid::parse(source, Ok)?;
+    recorder.record(Frame::new(&map.to_string()));
+
+    for (n, step) in path.windows(2).enumerate() {
+        let (from, to) = (step[0], step[1]);
+        map[from] = match (to.x - from.x, to.y - from.y) {
+            (1, 0) => '>',
+            (-1, 0) => '<',
+            (0, 1) => 'v',
+            _ => '^',
+        };
+        recorder.record(Frame::new(&map.to_string()).with_caption(format!("step {} of {}", n + 1, path.len() - 1)));
     }
 
-    fn neighbors(&self, x: usize, y: usize, grid: &[Vec<u16>]) -> Vec<(usize, usize)> {
-        let max_x = grid[0].len() - 1;
-        let max_y = grid.len() - 1;
+    Ok(())
+}
+
+fn successors(pos: &Pos, grid: &Grid<u16>) -> Vec<(Pos, u32)> {
+    let val = grid[*pos];
 
-        vec![
-            (x.saturating_sub(1), y),
-            (x, y.saturating_sub(1)),
-            (if x == max_x { x } else { x + 1 }, y),
-            (x, if y == max_y { y } else { y + 1 }),
-        ]
-    }
+    grid.neighbors4(*pos)
+        .filter(|p| grid[*p] <= (val + 1))
+        .map(|p| (p, 1))
+        .collect()
 }
 
-fn parse_grid(source: &str) -> (Vec<Vec<u16>>, Pos, Pos) {
-    let mut start = Pos(0, 0);
-    let mut end = Pos(0, 0);
-    (
-        source
-            .lines()
-            .enumerate()
-            .map(|(y, l)| {
-                l.chars()
-                    .enumerate()
-                    .map(|(x, c)| match c {
-                        'S' => {
-                            start = Pos(x, y);
-                            0
-                        }
-                        'E' => {
-                            end = Pos(x, y);
-                            27
-                        }
-                        'a'..='z' => (c as u16) - 96,
-                        _ => unimplemented!("Unknown char! {c}"),
-                    })
-                    .collect()
-            })
-            .collect(),
-        start,
-        end,
-    )
+fn parse_grid(source: &str) -> Result<(Grid<u16>, Pos, Pos)> {
+    let (grid, marks) = Grid::parse_marked(source, "SE", |c| match c {
+        'S' => Ok(0),
+        'E' => Ok(27),
+        'a'..='z' => Ok((c as u16) - 96),
+        _ => Err(eyre!("Unknown char! {c}")),
+    })?;
+
+    let start = marks.get('S').ok_or_else(|| eyre!("There is no start!"))?;
+    let end = marks.get('E').ok_or_else(|| eyre!("There is no end!"))?;
+
+    Ok((grid, start, end))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use aoc_common::frames::Frames;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "31");
-    }
+    aoc_common::examples!(Solver);
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "29");
+    fn test_parsing() {
+        let i = "SabczE";
+        let (r, s, e) = parse_grid(i).unwrap();
+        assert_eq!(r.height(), 1);
+        assert_eq!(s, Pos::new(0, 0));
+        assert_eq!(e, Pos::new(5, 0));
+        assert_eq!(r.width(), 6);
+        assert_eq!(r.row(0).copied().collect::<Vec<u16>>(), vec![0, 1, 2, 3, 26, 27]);
     }
 
     #[test]
-    fn test_parsing() {
-        let i = "SabczE";
-        let (r, s, e) = parse_grid(i);
-        assert_eq!(r.len(), 1);
-        assert_eq!(s, Pos(0, 0));
-        assert_eq!(e, Pos(5, 0));
-        assert_eq!(r[0].len(), 6);
-        assert_eq!(r[0], vec![0, 1, 2, 3, 26, 27]);
+    fn test_walk() {
+        let source = include_str!("../test_input.txt");
+        let mut frames = Frames::default();
+        assert_eq!(Solver.visualize(1, source, &mut frames).unwrap().unwrap(), Answer::Number(31));
+
+        assert_eq!(frames.0.len(), 32);
+        assert_eq!(frames.0[0].to_string(), source);
+        assert_eq!(frames.0[31].caption, "step 31 of 31");
+        assert_eq!(frames.0[31].lines.iter().flat_map(|l| l.chars()).filter(|c| "<>^v".contains(*c)).count(), 31);
+
+        assert!(Solver.visualize(3, source, &mut frames).is_none());
     }
 
     #[test]
     fn test_successors() {
         let i = "abc\nSfd\nqEe";
-        let (r, s, e) = parse_grid(i);
+        let (r, s, e) = parse_grid(i).unwrap();
 
-        assert_eq!(s, Pos(0, 1));
-        assert_eq!(e, Pos(1, 2));
+        assert_eq!(s, Pos::new(0, 1));
+        assert_eq!(e, Pos::new(1, 2));
 
-        let sc = s.successors(&r);
+        let sc = successors(&s, &r);
         assert_eq!(sc.len(), 1);
     }
 
@@ -164,11 +175,11 @@ mod tests {
     #[ignore] // rules for going to the end require the entire alphabet
     fn test_parsing_2() {
         let i = "abc\nSfd\nqEe";
-        let (r, s, e) = parse_grid(i);
-        assert_eq!(r.len(), 3);
-        assert_eq!(r[0].len(), 3);
+        let (r, s, e) = parse_grid(i).unwrap();
+        assert_eq!(r.height(), 3);
+        assert_eq!(r.width(), 3);
 
-        let res = astar(&s, |p| p.successors(&r), |p| p.distance(&e), |p| *p == e);
+        let res = astar(&s, |p| successors(p, &r), |p| p.manhattan(&e) as u32, |p| *p == e);
         assert_eq!(res.unwrap().1, 6);
     }
 }
@@ -0,0 +1,87 @@
+//! Random pairs of packets, `size` is the number of pairs, at least one.
+//!
+//! Like in the real inputs, lists hold up to five items and nest up to four levels deep, and no packet compares
+//! equal to a divider packet of part 2, which would be counted as one.
+
+use aoc_common::rand::Rng;
+
+use crate::Packet;
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", not_divider(rng), not_divider(rng)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn not_divider(rng: &mut impl Rng) -> Packet {
+    use Packet::*;
+
+    let dividers = [2, 6].map(|n| List(vec![List(vec![Number(n)])]));
+    loop {
+        let p = packet(rng, 4);
+        if !dividers.contains(&p) {
+            return p;
+        }
+    }
+}
+
+/// A random list packet nesting at most `depth` more lists.
+pub fn packet(rng: &mut impl Rng, depth: usize) -> Packet {
+    let items = (0..rng.gen_range(0..=5))
+        .map(|_| {
+            if depth > 0 && rng.gen_bool(0.3) {
+                packet(rng, depth - 1)
+            } else {
+                Packet::Number(rng.gen_range(0..=10))
+            }
+        })
+        .collect();
+
+    Packet::List(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pairs, part1, part2};
+    use aoc_common::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..20 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 50);
+            let (_, p) = pairs(&source).unwrap();
+            assert_eq!(p.len(), 50);
+            assert_eq!(
+                format!("{}\n{}", p[0].left, p[0].right),
+                source.lines().take(2).collect::<Vec<_>>().join("\n")
+            );
+            part1(&source).unwrap();
+            part2(&source).unwrap();
+        }
+
+        for size in [0, 1] {
+            let source = input(&mut StdRng::seed_from_u64(0), size);
+            assert_eq!(pairs(&source).unwrap().1.len(), 1);
+            part1(&source).unwrap();
+            part2(&source).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_dividers() {
+        use Packet::*;
+
+        let source = input(&mut StdRng::seed_from_u64(1), 1000);
+        let (_, pairs) = pairs(&source).unwrap();
+        let packets: Vec<Packet> = pairs.into_iter().flat_map(|p| [p.left, p.right]).collect();
+        let [two, six] = [2, 6].map(|n| List(vec![List(vec![Number(n)])]));
+        assert!(packets.iter().all(|p| p != &two && p != &six));
+
+        // `[[6]]` also comes after `[[2]]` itself
+        let a = packets.iter().filter(|&p| p < &two).count();
+        let b = packets.iter().filter(|&p| p < &six).count();
+        assert_eq!(part2(&source).unwrap(), ((a + 1) * (b + 2)).to_string());
+    }
+}
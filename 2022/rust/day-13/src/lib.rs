@@ -1,20 +1,25 @@
-use std::cmp::Ordering;
+pub mod generate;
+
+use aoc_common::{
+    parse::{finish, token, IResult},
+    rand::{rngs::StdRng, SeedableRng},
+    Answer, Solution,
+};
+use std::{cmp::Ordering, fmt, hint::black_box};
 
 use color_eyre::Result;
 use itertools::Itertools;
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::newline,
+    combinator::{all_consuming, cut},
     multi::{separated_list0, separated_list1},
     sequence::{delimited, separated_pair},
-    IResult, Parser,
+    Parser,
 };
 
 pub fn part1(source: &str) -> Result<String> {
-    Ok(pairs(source)
-        .expect("The input must be valid!")
-        .1
+    Ok(finish(source, pairs(source))?
         .iter()
         .enumerate()
         .map(|(i, p)| (i + 1, p))
@@ -27,9 +32,7 @@ pub fn part1(source: &str) -> Result<String> {
 pub fn part2(source: &str) -> Result<String> {
     use Packet::*;
 
-    Ok(pairs(source)
-        .expect("The input must be valid!")
-        .1
+    Ok(finish(source, pairs(source))?
         .into_iter()
         .flat_map(|p| [p.left, p.right])
         .chain([
@@ -47,6 +50,28 @@ pub fn part2(source: &str) -> Result<String> {
         .to_string())
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(finish(input, pairs(input)).map(|p| {
+            black_box(p);
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Pair {
     left: Packet,
@@ -91,35 +116,71 @@ impl Ord for Packet {
     }
 }
 
-pub fn packet(input: &str) -> IResult<&str, Packet> {
+/// Writes the packet the way the input does.
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Number(n) => write!(f, "{n}"),
+            Packet::List(items) => write!(f, "[{}]", items.iter().join(",")),
+        }
+    }
+}
+
+pub fn packet(input: &str) -> IResult<'_, Packet> {
     alt((
-        delimited(tag("["), separated_list0(tag(","), packet), tag("]")).map(Packet::List),
+        delimited(token("["), separated_list0(token(","), packet), token("]")).map(Packet::List),
         nom::character::complete::u32.map(Packet::Number),
     ))(input)
 }
 
-pub fn pairs(input: &str) -> IResult<&str, Vec<Pair>> {
-    separated_list1(
-        tag("\n\n"),
-        separated_pair(packet, newline, packet).map(|(left, right)| Pair { left, right }),
-    )(input)
+pub fn pairs(input: &str) -> IResult<'_, Vec<Pair>> {
+    all_consuming(separated_list1(
+        token("\n\n"),
+        cut(separated_pair(packet, newline, packet).map(|(left, right)| Pair { left, right })),
+    ))(input.trim_end())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use proptest::prelude::*;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "13");
+    aoc_common::examples!(Solver);
+
+    fn any_packet() -> impl Strategy<Value = Packet> {
+        let number = (0..20u32).prop_map(Packet::Number);
+        number.prop_recursive(4, 32, 5, |inner| {
+            prop::collection::vec(inner, 0..5).prop_map(Packet::List)
+        })
     }
 
-    #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "140");
+    proptest! {
+        #[test]
+        fn prop_packet_roundtrip(p in any_packet()) {
+            let source = p.to_string();
+            let (rest, parsed) = packet(&source).unwrap();
+            prop_assert_eq!(rest, "");
+            // not `==`, which would let a list of one number pass for the number
+            prop_assert_eq!(parsed.to_string(), source);
+        }
+
+        #[test]
+        fn prop_packet_order(a in any_packet(), b in any_packet(), c in any_packet()) {
+            // consistent with `Eq` and antisymmetric
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+
+            // transitive
+            if a <= b && b <= c {
+                prop_assert!(a <= c, "{a} <= {b} <= {c}");
+            }
+        }
+
+        #[test]
+        fn prop_pairs_no_panic(source in "[\\[\\]0-9,\n]{0,40}") {
+            let _ = pairs(&source);
+        }
     }
 
     #[test]
@@ -160,5 +221,14 @@ mod tests {
                 right: Packet::List(vec![Packet::List(vec![])])
             }
         );
+
+        // the trailing newline of the files is fine too
+        assert_eq!(pairs("[[[]]]\n[[]]\n").unwrap().1, p.1);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = part1("[1,[2]]\n[3]\n\n[1,2\n[3]").unwrap_err();
+        assert_eq!(e.to_string(), "Expected \"]\" at line 4, column 5");
     }
 }
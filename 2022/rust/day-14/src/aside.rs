@@ -1,11 +1,10 @@
 use super::*;
+use aoc_common::parse::{token, IResult};
 use nom::{
-    bytes::complete::tag,
     character::complete::newline,
-    combinator::{all_consuming, map},
+    combinator::{all_consuming, cut, map},
     multi::separated_list1,
     sequence::separated_pair,
-    IResult,
 };
 
 #[derive(Default, Debug, PartialEq)]
@@ -20,8 +19,7 @@ pub enum SandState {
 pub struct Sand {
     // cave: &'a Cave,
     pub state: SandState,
-    pub pos_x: usize,
-    pub pos_y: usize,
+    pub pos: Coord,
 }
 
 impl Sand {
@@ -29,14 +27,9 @@ impl Sand {
         Self {
             // cave,
             state: SandState::default(),
-            pos_x: cave.sand_source.0,
-            pos_y: cave.sand_source.1,
+            pos: cave.sand_source,
         }
     }
-
-    pub fn as_coord(&self) -> Coord {
-        Coord(self.pos_x, self.pos_y)
-    }
 }
 
 #[derive(Default, PartialEq, Clone, Copy)]
@@ -57,52 +50,67 @@ impl fmt::Debug for Point {
     }
 }
 
-#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-pub struct Coord(pub usize, pub usize);
-
-impl fmt::Debug for Coord {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.0, self.1)
-    }
-}
-
-impl std::ops::Sub for Coord {
-    type Output = Coord;
-
-    fn sub(self, other: Self) -> Self::Output {
-        Coord(self.0.abs_diff(other.0), self.1.abs_diff(other.1))
-    }
-}
+pub type Coord = Pos;
 
-pub fn parse(i: &str) -> IResult<&str, Vec<Vec<Coord>>> {
-    all_consuming(separated_list1(newline, parse_line))(i.trim_end())
+pub fn parse(i: &str) -> IResult<'_, Vec<Vec<Coord>>> {
+    all_consuming(separated_list1(newline, cut(parse_line)))(i.trim_end())
 }
 
-fn parse_line(i: &str) -> IResult<&str, Vec<Coord>> {
-    separated_list1(tag(" -> "), parse_coord)(i)
+fn parse_line(i: &str) -> IResult<'_, Vec<Coord>> {
+    separated_list1(token(" -> "), cut(parse_coord))(i)
 }
 
-fn parse_coord(i: &str) -> IResult<&str, Coord> {
+fn parse_coord(i: &str) -> IResult<'_, Coord> {
     map(
         separated_pair(
-            map(nom::character::complete::u32, |n| n as usize),
-            tag(","),
-            map(nom::character::complete::u32, |n| n as usize),
+            map(nom::character::complete::u32, |n| n as isize),
+            token(","),
+            map(nom::character::complete::u32, |n| n as isize),
         ),
-        |(x, y)| Coord(x, y),
+        |(x, y)| Coord::new(x, y),
     )(i)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::parse::finish;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = finish(&source, parse(&source));
+        }
+
+        #[test]
+        fn prop_parse_no_panic_near_miss(source in "[0-9, \\->\n]{0,60}") {
+            let _ = finish(&source, parse(&source));
+        }
+
+        #[test]
+        fn prop_parse_roundtrip(paths in prop::collection::vec(prop::collection::vec((0..1000u32, 0..1000u32), 1..6), 1..10)) {
+            let source = paths
+                .iter()
+                .map(|p| p.iter().map(|(x, y)| format!("{x},{y}")).collect::<Vec<_>>().join(" -> "))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let parsed = finish(&source, parse(&source)).unwrap();
+            let expected: Vec<Vec<Coord>> = paths
+                .iter()
+                .map(|p| p.iter().map(|&(x, y)| Coord::new(x as isize, y as isize)).collect())
+                .collect();
+            prop_assert_eq!(parsed, expected);
+        }
+    }
 
     #[test]
     fn test_offset_calc() {
         let i = "498,4 -> 498,6 -> 496,6";
         let r = parse_line(i).unwrap().1;
 
-        let offset = r.iter().map(|c| c.0).min().unwrap().to_owned();
+        let offset = r.iter().map(|c| c.x).min().unwrap().to_owned();
 
         assert_eq!(offset, 496);
     }
@@ -111,13 +119,16 @@ mod tests {
     fn test_parse_line() {
         let i = "498,4 -> 498,6 -> 496,6";
         let r = parse_line(i).unwrap().1;
-        assert_eq!(r, vec![Coord(498, 4), Coord(498, 6), Coord(496, 6)]);
+        assert_eq!(
+            r,
+            vec![Coord::new(498, 4), Coord::new(498, 6), Coord::new(496, 6)]
+        );
     }
 
     #[test]
     fn test_parse_coord() {
         let i = "123,4";
         let r = parse_coord(i).unwrap().1;
-        assert_eq!(r, Coord(123, 4));
+        assert_eq!(r, Coord::new(123, 4));
     }
 }
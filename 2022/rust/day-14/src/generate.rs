@@ -0,0 +1,74 @@
+//! Random rock paths, `size` is the number of paths, at least one.
+//!
+//! They stay within the area of the real inputs, below the sand source and well inside the cave's padding.
+
+use aoc_common::rand::Rng;
+
+const X: (isize, isize) = (440, 560);
+const Y: (isize, isize) = (13, 170);
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let (mut x, mut y) = (rng.gen_range(X.0..=X.1), rng.gen_range(Y.0..=Y.1));
+            let mut points = vec![format!("{x},{y}")];
+            let mut horizontal = rng.gen_bool(0.5);
+
+            for _ in 1..rng.gen_range(2..=6) {
+                // turn at every point, like the real paths do
+                let (c, (min, max)) = if horizontal { (&mut x, X) } else { (&mut y, Y) };
+                let step = rng.gen_range(1..=10) * if rng.gen_bool(0.5) { 1 } else { -1 };
+                *c = if (min..=max).contains(&(*c + step)) {
+                    *c + step
+                } else {
+                    *c - step
+                };
+
+                points.push(format!("{x},{y}"));
+                horizontal = !horizontal;
+            }
+
+            points.join(" -> ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2};
+    use aoc_common::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 100);
+            let (_, paths) = parse(&source).unwrap();
+            assert_eq!(paths.len(), 100);
+
+            let points = paths.iter().flatten();
+            assert!(points
+                .clone()
+                .all(|p| (X.0..=X.1).contains(&p.x) && (Y.0..=Y.1).contains(&p.y)));
+            // every path turns at each of its points
+            for path in &paths {
+                assert!(path
+                    .windows(2)
+                    .all(|w| (w[0].x == w[1].x) != (w[0].y == w[1].y)));
+            }
+
+            // with the rocks below the source, the floor of part 2 always holds more sand
+            let rest: usize = part1(&source).unwrap().parse().unwrap();
+            let floor: usize = part2(&source).unwrap().parse().unwrap();
+            assert!(0 < rest && rest < floor, "{rest} {floor}");
+        }
+
+        for size in [0, 1] {
+            let source = input(&mut StdRng::seed_from_u64(0), size);
+            assert_eq!(parse(&source).unwrap().1.len(), 1);
+            part1(&source).unwrap();
+            part2(&source).unwrap();
+        }
+    }
+}
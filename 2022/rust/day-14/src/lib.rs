@@ -1,34 +1,96 @@
 mod aside;
-
+pub mod generate;
+
+use aoc_common::{
+    parse::finish,
+    rand::{rngs::StdRng, SeedableRng},
+    Answer, Frame, Recorder, Solution,
+};
+use aoc_grid::{Direction, Grid, Pos};
 use aside::*;
 use color_eyre::Result;
 use itertools::{Itertools, MinMaxResult};
-use nom::Finish;
-use std::fmt;
+use std::{fmt, hint::black_box};
 
 pub fn part1(source: &str) -> Result<String> {
-    let parsed = parse(source).finish().unwrap().1;
-    let mut cave = Cave::with_rocks(parsed);
-
-    Ok(cave.calculate_sand().to_string())
+    Ok(cave(source, 1)?.calculate_sand().to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
-    let parsed = parse(source).finish().unwrap().1;
+    Ok(cave(source, 2)?.calculate_sand().to_string())
+}
+
+fn cave(source: &str, part: u8) -> Result<Cave> {
+    let parsed = finish(source, parse(source))?;
     let mut cave = Cave::with_rocks(parsed);
 
-    cave.grid.push(vec![Point::Rock; cave.grid[0].len()]);
+    if part == 2 {
+        // the floor is one row below the lowest row of air
+        let floor = Pos::new(cave.grid.max().x, cave.grid.max().y + 1);
+        cave.grid.grow_to_include(floor, Point::Rock);
+    }
+
+    Ok(cave)
+}
+
+/// Every grain at first, then fewer and fewer of them, as the pile only grows slowly once it is big.
+fn worth_a_frame(fallen: u32) -> bool {
+    fallen <= 100 || (fallen <= 1000 && fallen.is_multiple_of(10)) || fallen.is_multiple_of(100)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(finish(input, parse(input)).map(|p| {
+            black_box(p);
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn visualize(
+        &self,
+        part: u8,
+        input: &str,
+        recorder: &mut dyn Recorder,
+    ) -> Option<Result<Answer>> {
+        matches!(part, 1 | 2).then(|| {
+            cave(input, part).map(|mut cave| {
+                recorder.record(Frame::new(&cave.picture()));
+                let fallen = cave.calculate_sand_with(|cave, fallen| {
+                    if worth_a_frame(fallen) {
+                        recorder.record(
+                            Frame::new(&cave.picture())
+                                .with_caption(format!("{fallen} grains of sand")),
+                        );
+                    }
+                });
+                recorder.record(
+                    Frame::new(&cave.picture())
+                        .with_caption(format!("{fallen} grains of sand, done")),
+                );
 
-    Ok(cave.calculate_sand().to_string())
+                fallen.into()
+            })
+        })
+    }
 }
 
 #[derive(Default)]
 pub struct Cave {
     sand_source: Coord,
-    offset: usize,
-    max: usize,
-    extra: usize,
-    grid: Vec<Vec<Point>>,
+    grid: Grid<Point>,
 }
 
 impl Cave {
@@ -36,14 +98,19 @@ impl Cave {
         let mut cave = Cave {
             ..Default::default()
         };
-        cave.sand_source = Coord(500, 0);
-        cave.extra = 500; // padding
+        cave.sand_source = Coord::new(500, 0);
+        let extra = 500; // padding
 
-        let minmax = rocks.iter().flatten().map(|c| c.0).minmax();
+        let minmax = rocks.iter().flatten().map(|c| c.x).minmax();
+        let depth = rocks.iter().flatten().map(|c| c.y + 1).max().unwrap();
 
+        // fill the grid with air first
         if let MinMaxResult::MinMax(min, max) = minmax {
-            cave.offset = (min).saturating_sub(cave.extra);
-            cave.max = max;
+            cave.grid = Grid::with_bounds(
+                Pos::new(min - extra, 0),
+                Pos::new(max + extra, depth),
+                Point::Air,
+            );
         } else {
             unimplemented!("What in tarnation?!")
         }
@@ -54,23 +121,28 @@ impl Cave {
     }
 
     fn calculate_sand(&mut self) -> u32 {
+        self.calculate_sand_with(|_, _| {})
+    }
+
+    /// Pours the sand until it falls into the abyss or blocks the source, calling `on_rest` after each grain settles.
+    fn calculate_sand_with(&mut self, mut on_rest: impl FnMut(&Self, u32)) -> u32 {
         let mut fallen = 0;
         let mut sand_grain = Sand::new(self);
 
         'main: loop {
             while let Some(coord) = self.fall(&mut sand_grain) {
-                sand_grain.pos_x = coord.0;
-                sand_grain.pos_y = coord.1;
+                sand_grain.pos = coord;
             }
 
             match sand_grain.state {
                 SandState::Abyss => break,
                 SandState::Rest => {
                     fallen += 1;
-                    if sand_grain.as_coord() == self.sand_source {
+                    if sand_grain.pos == self.sand_source {
                         break 'main;
                     }
                     self.rest_sand(sand_grain);
+                    on_rest(self, fallen);
                     sand_grain = Sand::new(self);
                 }
                 _ => {}
@@ -80,29 +152,56 @@ impl Cave {
         fallen
     }
 
+    /// Like the puzzle draws it, only the columns with something in them and without the padding.
+    fn picture(&self) -> String {
+        let (min, max) = self
+            .grid
+            .iter()
+            .filter(|(_, point)| **point != Point::Air)
+            .map(|(p, _)| p.x)
+            .chain([self.sand_source.x])
+            .minmax()
+            .into_option()
+            .unwrap();
+
+        let mut picture = String::new();
+        for y in self.grid.min().y..=self.grid.max().y {
+            for x in min.max(self.grid.min().x + 1) - 1..=max.min(self.grid.max().x - 1) + 1 {
+                let p = Pos::new(x, y);
+                if p == self.sand_source {
+                    picture.push('+');
+                } else {
+                    picture += &format!("{:?}", self.grid[p]);
+                }
+            }
+            picture.push('\n');
+        }
+
+        picture
+    }
+
     fn fall(&mut self, grain: &mut Sand) -> Option<Coord> {
         use Point::*;
 
-        let x = grain.pos_x;
-        let y = grain.pos_y + 1;
+        let below = grain.pos + Direction::Down.vector();
 
-        if y == self.grid.len() {
+        if below.y > self.grid.max().y {
             grain.state = SandState::Abyss;
             return None;
         }
 
-        let space_below = self.get_below(x, y);
+        let space_below = self.get_below(below);
 
         if space_below[1] == &Air {
-            return Some(Coord(x, y));
+            return Some(below);
         }
 
         if space_below[0] == &Air {
-            return Some(Coord(x - 1, y));
+            return Some(below + Direction::Left.vector());
         }
 
         if space_below[2] == &Air {
-            return Some(Coord(x + 1, y));
+            return Some(below + Direction::Right.vector());
         }
 
         grain.state = SandState::Rest;
@@ -110,77 +209,53 @@ impl Cave {
     }
 
     fn rest_sand(&mut self, grain: Sand) {
-        self.grid[grain.pos_y][grain.pos_x - self.offset] = Point::Sand;
+        self.grid[grain.pos] = Point::Sand;
     }
 
-    fn get_below(&self, x: usize, y: usize) -> Vec<&Point> {
+    fn get_below(&self, below: Coord) -> Vec<&Point> {
         // need to return the three spaces below the current position
-        let x = x - self.offset;
-        vec![&self.grid[y][x - 1], &self.grid[y][x], &self.grid[y][x + 1]]
+        vec![
+            &self.grid[below + Direction::Left.vector()],
+            &self.grid[below],
+            &self.grid[below + Direction::Right.vector()],
+        ]
     }
 
     fn add_rocks(&mut self, rocks: Vec<Vec<Coord>>) {
-        // fill the grid with air first
-
-        let depth = rocks.iter().flatten().map(|c| c.1 + 1).max().unwrap();
-        for y in 0..=depth {
-            self.grid.push(vec![]);
-            for _ in 0..=((self.max - self.offset) + self.extra) {
-                self.grid[y].push(Point::Air);
-            }
-        }
-
-        // then add rocks
         for line in rocks {
             line.iter().tuple_windows().for_each(|(left, right)| {
-                let diff = *left - *right;
-                match (diff.0, diff.1) {
-                    (0, _) => {
-                        // vertical diff
-                        let (y1, y2) = if left.1 > right.1 {
-                            (right.1, left.1)
-                        } else {
-                            (left.1, right.1)
-                        };
-
-                        for y in y1..=y2 {
-                            self.add_rock(Coord(left.0, y));
-                        }
-                    }
-                    (_, 0) => {
-                        // horizontal diff
-                        let (x1, x2) = if left.0 > right.0 {
-                            (right.0, left.0)
-                        } else {
-                            (left.0, right.0)
-                        };
-
-                        for x in x1..=x2 {
-                            self.add_rock(Coord(x, left.1));
-                        }
-                    }
-                    (_, _) => unimplemented!("What in tarnation?!"),
-                };
+                let step = (*right - *left).signum();
+                if step.x != 0 && step.y != 0 {
+                    unimplemented!("What in tarnation?!")
+                }
+
+                let mut rock = *left;
+                self.add_rock(rock);
+                while rock != *right {
+                    rock += step;
+                    self.add_rock(rock);
+                }
             })
         }
     }
 
     fn add_rock(&mut self, rock: Coord) {
-        self.grid[rock.1][rock.0 - self.offset] = Point::Rock;
+        self.grid[rock] = Point::Rock;
     }
 }
 
 impl fmt::Display for Cave {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for y in 0..self.grid.len() {
-            for x in 0..self.grid[y].len() {
-                if Coord(x + self.offset, y) == self.sand_source {
-                    write!(f, "+")?;
-                } else {
-                    write!(f, "{:?}", self.grid[y][x])?;
-                }
+        for (p, point) in self.grid.iter() {
+            if p == self.sand_source {
+                write!(f, "+")?;
+            } else {
+                write!(f, "{point:?}")?;
+            }
+
+            if p.x == self.grid.max().x {
+                writeln!(f)?;
             }
-            writeln!(f)?;
         }
 
         Ok(())
@@ -191,21 +266,22 @@ impl fmt::Debug for Cave {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Cave")
             .field("sand_source", &self.sand_source)
-            .field("offset", &self.offset)
-            .field("max", &self.max)
+            .field("min", &self.grid.min())
+            .field("max", &self.grid.max())
             .finish()?;
 
         // the playing field
         writeln!(f)?;
-        for y in 0..self.grid.len() {
-            for x in 0..self.grid[y].len() {
-                if Coord(x + self.offset, y) == self.sand_source {
-                    write!(f, "+")?;
-                } else {
-                    write!(f, "{:?}", self.grid[y][x])?;
-                }
+        for (p, point) in self.grid.iter() {
+            if p == self.sand_source {
+                write!(f, "+")?;
+            } else {
+                write!(f, "{point:?}")?;
+            }
+
+            if p.x == self.grid.max().x {
+                writeln!(f)?;
             }
-            writeln!(f)?;
         }
 
         Ok(())
@@ -215,17 +291,52 @@ impl fmt::Debug for Cave {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use aoc_common::frames::Frames;
+
+    aoc_common::examples!(Solver);
+
+    #[test]
+    fn test_visualize() {
+        let source = include_str!("../test_input.txt");
+        let mut frames = Frames::default();
+        assert_eq!(
+            Solver.visualize(1, source, &mut frames).unwrap().unwrap(),
+            Answer::Number(24)
+        );
+
+        assert_eq!(frames.0.len(), 26);
+        assert_eq!(frames.0[25].caption, "24 grains of sand, done");
+        assert!(Solver.visualize(3, source, &mut frames).is_none());
+        assert_eq!(
+            frames.0[25].to_string(),
+            "\
+.......+....
+............
+.......o....
+......ooo...
+.....#ooo##.
+....o#ooo#..
+...###ooo#..
+.....oooo#..
+..o.ooooo#..
+.#########..
+............
+24 grains of sand, done
+"
+        );
+    }
 
     #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "24");
+    fn test_worth_a_frame() {
+        assert_eq!(
+            (1..=10_000).filter(|n| worth_a_frame(*n)).count(),
+            100 + 90 + 90
+        );
     }
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "93");
+    fn test_parse_error() {
+        let e = part1("498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502;9 -> 494,9").unwrap_err();
+        assert_eq!(e.to_string(), "Expected \",\" at line 2, column 22");
     }
 }
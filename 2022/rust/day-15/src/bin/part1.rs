@@ -1,12 +1,14 @@
-use advent_of_code_2022::part1;
+use aoc_2022_day_15::part1;
+use aoc_common::Params;
 use color_eyre::Result;
-use std::fs;
+use std::{fs, path::Path};
 
 fn main() -> Result<()> {
     color_eyre::install().unwrap();
 
     let file = fs::read_to_string("./real_input.txt").unwrap();
-    println!("{}", part1(&file)?);
+    let params = Params::load(Path::new("."), Path::new("./real_input.txt"))?;
+    println!("{}", part1(&file, &params)?);
 
     Ok(())
 }
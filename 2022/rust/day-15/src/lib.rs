@@ -1,13 +1,13 @@
 mod parser;
 
-use std::collections::BTreeSet;
+use aoc_common::{geometry::Point2, parse::finish, Answer, Params, Solution};
+use std::{collections::BTreeSet, hint::black_box};
 
 use color_eyre::Result;
-use nom::Finish;
 use parser::*;
 
-pub fn part1(source: &str) -> Result<String> {
-    Ok(solve(source, 2000000))
+pub fn part1(source: &str, params: &Params) -> Result<String> {
+    solve(source, params.optional("row").unwrap_or(2_000_000) as i32)
 }
 
 pub fn part2(_source: &str) -> Result<String> {
@@ -16,11 +16,30 @@ pub fn part2(_source: &str) -> Result<String> {
     Ok(res.to_string())
 }
 
-fn solve(source: &str, row: i32) -> String {
-    (parse(source)
-        .finish()
-        .unwrap()
-        .1
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(finish(input, parse(input)).map(|p| {
+            black_box(p);
+        }))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        self.part1_with(input, &Params::new())
+    }
+
+    fn part1_with(&self, input: &str, params: &Params) -> Result<Answer> {
+        Ok(part1(input, params)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+fn solve(source: &str, row: i32) -> Result<String> {
+    Ok((finish(source, parse(source))?
         .into_iter()
         .map(Sensor::new)
         .flat_map(|s| s.intersection(row))
@@ -30,30 +49,10 @@ fn solve(source: &str, row: i32) -> String {
         })
         .len()
         - 1)
-    .to_string()
+    .to_string())
 }
 
-#[derive(Debug, PartialEq, Ord, PartialOrd, Eq)]
-pub struct Point(i32, i32);
-
-impl Point {
-    fn manhattan_distance(&self, other: &Point) -> i32 {
-        (self.0 - other.0).abs() + (self.1 - other.1).abs()
-    }
-
-    fn intersection(&self, y: i32, distance: i32) -> Vec<Point> {
-        let mut points = Vec::new();
-
-        for x in (self.0 - distance)..=(self.0 + distance) {
-            let candidate = Point(x, y);
-            if self.manhattan_distance(&candidate) <= distance {
-                points.push(candidate);
-            }
-        }
-
-        points
-    }
-}
+pub type Point = Point2<i32>;
 
 #[derive(Debug)]
 pub struct Sensor {
@@ -64,31 +63,40 @@ pub struct Sensor {
 impl Sensor {
     fn new(from: (Point, Point)) -> Self {
         Self {
-            beacon_distance: from.0.manhattan_distance(&from.1),
+            beacon_distance: from.0.manhattan(&from.1),
             position: from.0,
         }
     }
 
+    /// The points of row `y` that are at most as far from the sensor as its beacon.
     fn intersection(&self, y: i32) -> Vec<Point> {
-        self.position.intersection(y, self.beacon_distance)
+        let (p, distance) = (self.position, self.beacon_distance);
+        let mut points = Vec::new();
+
+        for x in (p.x - distance)..=(p.x + distance) {
+            let candidate = Point::new(x, y);
+            if p.manhattan(&candidate) <= distance {
+                points.push(candidate);
+            }
+        }
+
+        points
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(solve(&source, 10), "26");
-    }
+    aoc_common::examples!(Solver);
 
     #[test]
-    #[ignore]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "1234");
+    fn test_parse_error() {
+        let source = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16: closest beacon at x=10, y=16\n";
+        let e = solve(source, 10).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Expected \"closest beacon is at \" at line 2, column 22"
+        );
     }
 }
@@ -1,51 +1,71 @@
 use super::*;
+use aoc_common::parse::{token, IResult};
 use nom::{
-    bytes::complete::tag,
     character::complete::newline,
-    combinator::{all_consuming, map},
+    combinator::{all_consuming, cut, map},
     multi::separated_list1,
     sequence::{preceded, separated_pair},
-    IResult,
 };
 
-pub fn parse(i: &str) -> IResult<&str, Vec<(Point, Point)>> {
-    all_consuming(separated_list1(newline, parse_line))(i.trim_end())
+pub fn parse(i: &str) -> IResult<'_, Vec<(Point, Point)>> {
+    all_consuming(separated_list1(newline, cut(parse_line)))(i.trim_end())
 }
 
-fn parse_line(i: &str) -> IResult<&str, (Point, Point)> {
+fn parse_line(i: &str) -> IResult<'_, (Point, Point)> {
     separated_pair(
-        preceded(tag("Sensor at "), parse_point),
-        tag(": "),
-        preceded(tag("closest beacon is at "), parse_point),
+        preceded(token("Sensor at "), parse_point),
+        token(": "),
+        preceded(token("closest beacon is at "), parse_point),
     )(i)
 }
 
-fn parse_point(i: &str) -> IResult<&str, Point> {
+fn parse_point(i: &str) -> IResult<'_, Point> {
     map(
         separated_pair(
-            preceded(tag("x="), nom::character::complete::i32),
-            tag(", "),
-            preceded(tag("y="), nom::character::complete::i32),
+            preceded(token("x="), nom::character::complete::i32),
+            token(", "),
+            preceded(token("y="), nom::character::complete::i32),
         ),
-        |(x, y)| Point(x, y),
+        |(x, y)| Point::new(x, y),
     )(i)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::parse::finish;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_parse_roundtrip(pairs in prop::collection::vec(any::<((i32, i32), (i32, i32))>(), 1..10)) {
+            let source = pairs
+                .iter()
+                .map(|((sx, sy), (bx, by))| format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let expected: Vec<_> = pairs.iter().map(|&(s, b)| (Point::from(s), Point::from(b))).collect();
+
+            prop_assert_eq!(finish(&source, parse(&source)).unwrap(), expected);
+        }
+
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = finish(&source, parse(&source));
+        }
+    }
 
     #[test]
     fn test_parse_point() {
         let i = "x=2, y=18";
         let r = parse_point(i).unwrap().1;
-        assert_eq!(r, Point(2, 18));
+        assert_eq!(r, Point::new(2, 18));
     }
 
     #[test]
     fn test_parse_line() {
         let i = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
         let r = parse_line(i).unwrap().1;
-        assert_eq!(r, (Point(2, 18), Point(-2, 15)));
+        assert_eq!(r, (Point::new(2, 18), Point::new(-2, 15)));
     }
 }
@@ -0,0 +1,101 @@
+//! Random valve graphs, `size` is the number of valves, from two up to 676 when the two-letter names run out.
+//!
+//! The tunnels go both ways and connect every valve to `AA`, about a quarter of the valves have a flow rate.
+
+use std::collections::BTreeSet;
+
+use aoc_common::rand::{seq::SliceRandom, Rng};
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    let mut names: Vec<String> = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .filter(|n| n != "AA")
+        .collect();
+    names.shuffle(rng);
+    names.truncate(size.clamp(2, 676) - 1);
+    names.insert(0, "AA".to_string());
+
+    let mut tunnels = vec![BTreeSet::new(); names.len()];
+    let mut connect = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+
+    // a random spanning tree, then a few shortcuts
+    for i in 1..names.len() {
+        connect(i, rng.gen_range(0..i));
+    }
+    for _ in 0..names.len() / 3 {
+        connect(rng.gen_range(0..names.len()), rng.gen_range(0..names.len()));
+    }
+
+    let mut lines: Vec<String> = names
+        .iter()
+        .zip(&tunnels)
+        .enumerate()
+        .map(|(i, (name, to))| {
+            let rate = if i > 0 && rng.gen_bool(0.25) {
+                rng.gen_range(1..=25)
+            } else {
+                0
+            };
+            let to: Vec<&str> = to.iter().map(|&t| names[t].as_str()).collect();
+            let lead = if to.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+
+            format!(
+                "Valve {name} has flow rate={rate}; {lead} {}",
+                to.join(", ")
+            )
+        })
+        .collect();
+    lines.shuffle(rng);
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use aoc_common::rand::{rngs::StdRng, SeedableRng};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..10 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 30);
+            let (_, nodes) = parse(&source).unwrap();
+            assert_eq!(nodes.len(), 30);
+            assert!(nodes.iter().any(|n| n.flow_rate > 0));
+
+            let tunnels: HashMap<&str, &[String]> = nodes
+                .iter()
+                .map(|n| (n.name.as_str(), &n.tunnels[..]))
+                .collect();
+            for (from, to) in &tunnels {
+                assert!(to
+                    .iter()
+                    .all(|t| tunnels[t.as_str()].iter().any(|b| b == from)));
+            }
+
+            // every valve can be reached from `AA`
+            let mut seen = HashSet::from(["AA"]);
+            let mut todo = vec!["AA"];
+            while let Some(v) = todo.pop() {
+                todo.extend(
+                    tunnels[v]
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|t| seen.insert(t)),
+                );
+            }
+            assert_eq!(seen.len(), 30);
+        }
+    }
+}
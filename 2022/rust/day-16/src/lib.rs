@@ -1,12 +1,19 @@
+pub mod generate;
 mod parser;
+use aoc_common::{
+    parse::finish,
+    rand::{rngs::StdRng, SeedableRng},
+    tracing::trace,
+    Answer, Solution,
+};
 use parser::*;
 
-use std::collections::{HashMap, BTreeSet};
+use std::{collections::{HashMap, BTreeSet}, hint::black_box};
 
 use color_eyre::Result;
 
 pub fn part1(source: &str) -> Result<String> {
-    let nodes = parse(source).unwrap().1;
+    let nodes = finish(source, parse(source))?;
 
     let mut graph = Graph::new();
     for node in nodes.clone() {
@@ -24,13 +31,34 @@ pub fn part1(source: &str) -> Result<String> {
     Ok(graph.max_flow("AA").to_string())
 }
 
-
 pub fn part2(_source: &str) -> Result<String> {
     let res = "";
 
     Ok(res.to_string())
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(finish(input, parse(input)).map(|p| {
+            black_box(p);
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Node {
     name: String,
@@ -93,7 +121,7 @@ impl Graph {
 
         while let Some((node, time, flow)) = stack.pop() {
             if time >= 30 {
-                dbg!(&node, &time, stack.len());
+                trace!(node, time, stack = stack.len(), "out of time");
                 max_flow = std::cmp::max(max_flow, flow);
                 continue;
             }
@@ -120,18 +148,6 @@ impl Graph {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "1651");
-    }
-
-    #[test]
-    #[ignore]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "1234");
-    }
+    aoc_common::examples!(Solver);
 }
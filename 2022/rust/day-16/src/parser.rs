@@ -1,21 +1,22 @@
-use nom::{IResult, multi::{separated_list1}, combinator::all_consuming, character::complete::{newline, alpha1, line_ending}, sequence::{preceded, delimited}, bytes::complete::tag, branch::alt};
+use aoc_common::parse::{token, IResult};
+use nom::{multi::{separated_list1}, combinator::{all_consuming, cut}, character::complete::{alpha1, line_ending}, sequence::{preceded, delimited}, branch::alt};
 
 use super::*;
 
-pub fn parse(i: &str) -> IResult<&str, Vec<Node>> {
-    all_consuming(separated_list1(line_ending, parse_line))(i.trim_end())
+pub fn parse(i: &str) -> IResult<'_, Vec<Node>> {
+    all_consuming(separated_list1(line_ending, cut(parse_line)))(i.trim_end())
 }
 
-fn parse_line(i: &str) -> IResult<&str, Node> {
-    let (i, name) = delimited(tag("Valve "), alpha1, tag(" has "))(i)?;
+fn parse_line(i: &str) -> IResult<'_, Node> {
+    let (i, name) = delimited(token("Valve "), alpha1, token(" has "))(i)?;
     let (i, flow_rate) = delimited(
-        tag("flow rate="),
+        token("flow rate="),
         nom::character::complete::u32,
-        tag(";")
+        token(";")
     )(i)?;
     let (i, tunnels) = preceded(
-        alt((tag(" tunnels lead to valves "), tag(" tunnel leads to valve "))),
-        separated_list1(tag(", "), alpha1)
+        alt((token(" tunnels lead to valves "), token(" tunnel leads to valve "))),
+        separated_list1(token(", "), alpha1)
     )(i)?;
 
     Ok((i,
@@ -30,6 +31,30 @@ fn parse_line(i: &str) -> IResult<&str, Node> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::parse::finish;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_parse_roundtrip(valves in prop::collection::vec(("[A-Z]{2}", any::<u32>(), prop::collection::vec("[A-Z]{2}", 1..5)), 1..10)) {
+            let source = valves
+                .iter()
+                .map(|(name, rate, tunnels)| match &tunnels[..] {
+                    [t] => format!("Valve {name} has flow rate={rate}; tunnel leads to valve {t}"),
+                    _ => format!("Valve {name} has flow rate={rate}; tunnels lead to valves {}", tunnels.join(", ")),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let expected: Vec<_> = valves.into_iter().map(|(name, rate, tunnels)| Node::new(name, rate, tunnels)).collect();
+
+            prop_assert_eq!(finish(&source, parse(&source)).unwrap(), expected);
+        }
+
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = finish(&source, parse(&source));
+        }
+    }
 
     #[test]
     fn test_parse_line() {
@@ -1,16 +1,17 @@
-use std::{num::ParseIntError, str::FromStr};
+use aoc_common::{Answer, Solution};
+use std::{hint::black_box, num::ParseIntError, str::FromStr};
 
-use color_eyre::Result;
+use color_eyre::{eyre::WrapErr, Result};
 
 pub fn part1(source: &str) -> Result<String> {
-    let mut vals = parse(source);
+    let mut vals = parse(source)?;
     sort_all(&mut vals);
 
     Ok(get_result(&vals).to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
-    let mut vals: Vec<Value> = parse(source)
+    let mut vals: Vec<Value> = parse(source)?
         .iter()
         .map(|vl| Value {
             v: vl.v * 811589153,
@@ -26,6 +27,24 @@ pub fn part2(source: &str) -> Result<String> {
     Ok(get_result(&vals).to_string())
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse(input).map(|vals| {
+            black_box(vals);
+        }))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
 fn get_result(vals: &[Value]) -> i64 {
     let (ix, _) = vals
         .iter()
@@ -45,14 +64,15 @@ fn git(n: usize, ix: usize, vals: &[Value]) -> usize {
     (n + ix) % vals.len()
 }
 
-fn parse(source: &str) -> Vec<Value> {
+fn parse(source: &str) -> Result<Vec<Value>> {
     source
         .lines()
         .enumerate()
-        .map(|l| {
-            let mut v = l.1.parse::<Value>().expect("Must be valid!");
-            v.index = l.0;
-            v
+        .map(|(index, l)| {
+            let v = l
+                .parse::<Value>()
+                .wrap_err_with(|| format!("Line {} is not a number: {l:?}", index + 1))?;
+            Ok(Value { index, ..v })
         })
         .collect()
 }
@@ -68,7 +88,7 @@ impl FromStr for Value {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
-            v: s.parse().expect("Must be a valid i64!"),
+            v: s.parse()?,
             index: 0,
         })
     }
@@ -104,17 +124,7 @@ mod tests {
     use super::*;
     use std::fs;
 
-    #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part1(&source).unwrap(), "3");
-    }
-
-    #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "1623178306");
-    }
+    aoc_common::examples!(Solver);
 
     fn pp(vals: &[Value]) {
         println!(
@@ -153,7 +163,7 @@ mod tests {
     }
 
     fn std_vals() -> Vec<Value> {
-        vec![1, 2, -3, 3, -2, 0, 4]
+        [1, 2, -3, 3, -2, 0, 4]
             .iter()
             .enumerate()
             .map(|(i, &v)| Value { v, index: i })
@@ -164,12 +174,13 @@ mod tests {
     fn test_parse() {
         let source = fs::read_to_string("./test_input.txt").unwrap();
         let vals: Vec<Value> = std_vals();
-        assert_eq!(parse(&source), vals)
+        assert_eq!(parse(&source).unwrap(), vals);
+        assert!(parse("1\nx\n").is_err());
     }
 
     #[test]
     fn test_swap_one() {
-        let mut s = parse("1\n2\n3\n");
+        let mut s = parse("1\n2\n3\n").unwrap();
         let r = vec![
             Value { v: 2, index: 1 },
             Value { v: 1, index: 0 },
@@ -181,7 +192,7 @@ mod tests {
 
     #[test]
     fn test_wrap_negative() {
-        let mut s = parse("4\n-2\n5\n6");
+        let mut s = parse("4\n-2\n5\n6").unwrap();
         let r = vec![
             Value { v: 4, index: 0 },
             Value { v: 5, index: 2 },
@@ -194,7 +205,7 @@ mod tests {
 
     #[test]
     fn test_wrap_neg_2() {
-        let mut s = parse("4\n5\n-2\n6");
+        let mut s = parse("4\n5\n-2\n6").unwrap();
         let r = vec![
             Value { v: -2, index: 2 },
             Value { v: 4, index: 0 },
@@ -207,7 +218,7 @@ mod tests {
 
     #[test]
     fn test_swap_boundary() {
-        let mut s = parse("2\n3\n1\n");
+        let mut s = parse("2\n3\n1\n").unwrap();
         let r = vec![
             Value { v: 2, index: 0 },
             Value { v: 1, index: 2 },
@@ -219,7 +230,7 @@ mod tests {
 
     #[test]
     fn test_swap_zero() {
-        let mut s = parse("1\n0\n3\n");
+        let mut s = parse("1\n0\n3\n").unwrap();
         let r = vec![
             Value { v: 1, index: 0 },
             Value { v: 0, index: 1 },
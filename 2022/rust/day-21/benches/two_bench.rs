@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use advent_of_code_2022 as aoc;
+use aoc_2022_day_21 as aoc;
 
 pub fn bench_part2_imp(c: &mut Criterion) {
     c.bench_function("solve imp", |b| b.iter(|| aoc::part2_bench(black_box(true))));
@@ -0,0 +1,163 @@
+//! Random monkey expression trees, `size` is roughly the number of monkeys.
+//!
+//! Like the real inputs, every division is exact, `humn` appears once, on the left of `root`,
+//! and the right side is what the left one yields for the number `humn` yells, so part 2 always has an answer.
+
+use std::collections::HashSet;
+
+use aoc_common::rand::{seq::SliceRandom, Rng};
+
+struct Tree<'a, R> {
+    rng: &'a mut R,
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl<R: Rng> Tree<'_, R> {
+    fn name(&mut self) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| char::from(b'a' + self.rng.gen_range(0..26)))
+                .collect();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn yell(&mut self, name: &str, job: String) {
+        self.lines.push(format!("{name}: {job}"));
+    }
+
+    /// A subtree of about `budget` monkeys that yells the positive `value`.
+    fn subtree(&mut self, value: i64, budget: usize) -> String {
+        let name = self.name();
+        if budget < 3 {
+            self.yell(&name, value.to_string());
+            return name;
+        }
+
+        let divisor = (2..=10)
+            .filter(|d| value % d == 0 && *d < value)
+            .collect::<Vec<_>>();
+        let (left, op, right) = match self.rng.gen_range(0..4) {
+            0 if value > 1 => {
+                let a = self.rng.gen_range(1..value);
+                (a, '+', value - a)
+            }
+            1 if !divisor.is_empty() => {
+                let d = *divisor.choose(self.rng).unwrap();
+                (value / d, '*', d)
+            }
+            2 if value < 1_000_000 => {
+                let d = self.rng.gen_range(2..=10);
+                (value * d, '/', d)
+            }
+            _ => {
+                let b = self.rng.gen_range(1..=100);
+                (value + b, '-', b)
+            }
+        };
+
+        let half = (budget - 1) / 2;
+        let (l, r) = (
+            self.subtree(left, budget - 1 - half),
+            self.subtree(right, half),
+        );
+        self.yell(&name, format!("{l} {op} {r}"));
+        name
+    }
+}
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    let humn: i64 = rng.gen_range(1..=10_000);
+    let mut tree = Tree {
+        rng,
+        names: HashSet::new(),
+        lines: vec![format!("humn: {humn}")],
+    };
+
+    // the operations between `humn` and `root`, small enough for the doubling search of part 2 not to overflow
+    let depth = (size / 4).clamp(1, 10);
+    let budget = size.saturating_sub(2 * depth + 2) / (depth + 1);
+    let (mut path, mut value) = ("humn".to_string(), humn);
+
+    for _ in 0..depth {
+        let divisor = (2..=5).filter(|d| value % d == 0).collect::<Vec<_>>();
+        let (other, op, next, humn_left) = match tree.rng.gen_range(0..5) {
+            0 => {
+                let x = tree.rng.gen_range(1..=1000);
+                (x, '+', value + x, tree.rng.gen_bool(0.5))
+            }
+            1 if value > 1 => {
+                let x = tree.rng.gen_range(1..value);
+                (x, '-', value - x, true)
+            }
+            2 => {
+                // the other side minus `humn`'s, the answer shrinks as `humn` grows
+                let x = value + tree.rng.gen_range(1..=1000);
+                (x, '-', x - value, false)
+            }
+            3 if !divisor.is_empty() => {
+                let d = *divisor.choose(tree.rng).unwrap();
+                (d, '/', value / d, true)
+            }
+            _ => {
+                let x = tree.rng.gen_range(2..=5);
+                (x, '*', value * x, tree.rng.gen_bool(0.5))
+            }
+        };
+
+        let other = tree.subtree(other, budget);
+        let name = tree.name();
+        let (l, r) = if humn_left {
+            (&path, &other)
+        } else {
+            (&other, &path)
+        };
+        tree.yell(&name, format!("{l} {op} {r}"));
+        (path, value) = (name, next);
+    }
+
+    let right = tree.subtree(value, budget);
+    tree.yell("root", format!("{path} + {right}"));
+
+    let Tree { rng, mut lines, .. } = tree;
+    lines.shuffle(rng);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2};
+    use aoc_common::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..20 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 200);
+            assert!(parse(&source).unwrap().len() > 100);
+
+            // several numbers can give the same result after a division, the lowest is the answer
+            let answer: i64 = part2(&source).unwrap().parse().unwrap();
+            assert_eq!(balance(&source, answer), 0);
+            assert_ne!(balance(&source, answer - 1), 0);
+        }
+    }
+
+    /// How much more the left side of `root` yells than the right one, when `humn` yells `humn`.
+    fn balance(source: &str, humn: i64) -> i64 {
+        let source = source
+            .lines()
+            .map(|l| match l.split_once(": ") {
+                Some(("humn", _)) => format!("humn: {humn}"),
+                Some(("root", job)) => format!("root: {}", job.replacen(['+', '*', '/'], "-", 1)),
+                _ => l.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        part1(&source).unwrap().parse().unwrap()
+    }
+}
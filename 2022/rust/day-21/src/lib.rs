@@ -1,10 +1,23 @@
-use std::{collections::{BTreeMap, VecDeque}, cmp::Ordering::{Greater, Less, Equal}};
+pub mod generate;
+
+use aoc_common::{
+    eyre::eyre,
+    parse::{finish, token, IResult},
+    rand::{rngs::StdRng, SeedableRng},
+    Answer, Solution,
+};
+use std::{collections::{BTreeMap, VecDeque}, cmp::Ordering::{Greater, Less}, hint::black_box};
 
 use color_eyre::Result;
-use nom::{bytes::complete::tag, character::complete::alpha1, sequence::terminated, IResult};
+use nom::{
+    branch::alt,
+    character::complete::{alpha1, one_of},
+    combinator::{all_consuming, map},
+    sequence::{delimited, terminated, tuple},
+};
 
 pub fn part1(source: &str) -> Result<String> {
-    let res = solve(source, 2326);
+    let res = solve(source)?;
     let res = res.0.resolve(res.1, res.2);
     Ok(res.to_string())
 }
@@ -14,68 +27,88 @@ pub fn part2(source: &str) -> Result<String> {
     // there are multiple values that produce this equality -> the above..=the above+2
     // the lowest number seems to be the correct solution
 
-    let monkeys = parse(source);
-    let (mut nums, ops) = transform(monkeys);
-    nums.insert("humn".to_string(), 2_326);
-    let res = solve_inner(nums.clone(), ops.clone());
+    let (mut f, target) = humn_search(source)?;
 
-    let target = res.2; // determined by manual testing
-
-    // the bisection works well, in both implementations
-    // the problem lies in the test input when compared with the real input
     // in the test input, the output of the 'solve_inner' method is related to its input
     // in the real input, the solution is **inversely** proportional to the input
-    let res = bisection_imp(
-        |n| {
-            nums.insert("humn".to_string(), n);
-            solve_inner(nums.clone(), ops.clone()).1
-        },
-        target,
-        0,
-        4_000_000_000_000, // the high bound was determined by some manual testing
-    );
+    // so the bisection finds out the direction on its own, within a bound found by doubling
+    let high = upper_bound(&mut f, target);
+    let res = bisection_imp(f, target, 0, high);
 
     Ok(res.to_string())
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        Some(parse(input).map(|monkeys| {
+            black_box(monkeys);
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        Some(generate::input(&mut StdRng::seed_from_u64(seed), size))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
 pub fn part2_bench(imp: bool) {
     let source = include_str!("../real_input.txt");
 
-    let monkeys = parse(source);
-    let (mut nums, ops) = transform(monkeys);
-    nums.insert("humn".to_string(), 2_326);
-    let res = solve_inner(nums.clone(), ops.clone());
-    let target = res.2;
+    let (mut f, target) = humn_search(source).unwrap();
+    let high = upper_bound(&mut f, target);
 
     if imp {
-        bisection_imp(
-            |n| {
-                nums.insert("humn".to_string(), n);
-                solve_inner(nums.clone(), ops.clone()).1
-            },
-            target,
-            0,
-            4_000_000_000_000,
-        );
+        bisection_imp(f, target, 0, high);
     } else {
-        bisection(
-            |n| {
-                nums.insert("humn".to_string(), n);
-                solve_inner(nums.clone(), ops.clone()).1
-            },
-            target,
-            0,
-            4_000_000_000_000,
-        );
+        bisection(f, target, 0, high);
     }
 }
 
-fn solve(source: &str, humn: i64) -> (MonkeyInfo, i64, i64) {
-    let monkeys = parse(source);
+/// What the left side of `root` yields for a number yelled by `humn`, and the right side it has to match.
+fn humn_search(source: &str) -> Result<(impl FnMut(i64) -> i64, i64)> {
+    let monkeys = parse(source)?;
+    let humn = humn(&monkeys)?;
+    let (mut nums, ops) = transform(monkeys);
+    nums.insert("humn".to_string(), humn);
+    let res = solve_inner(nums.clone(), ops.clone());
+
+    let target = res.2; // the right side does not depend on `humn`
+
+    let f = move |n| {
+        nums.insert("humn".to_string(), n);
+        solve_inner(nums.clone(), ops.clone()).1
+    };
+
+    Ok((f, target))
+}
+
+fn solve(source: &str) -> Result<(MonkeyInfo, i64, i64)> {
+    let monkeys = parse(source)?;
+    let humn = humn(&monkeys)?;
     let (mut nums, ops) = transform(monkeys);
 
     nums.insert("humn".to_string(), humn);
-    solve_inner(nums, ops)
+    Ok(solve_inner(nums, ops))
+}
+
+/// The number the `humn` monkey yells in the input, `transform` leaves it out.
+fn humn(monkeys: &[Monkey]) -> Result<i64> {
+    monkeys
+        .iter()
+        .find_map(|m| match m {
+            Monkey::Number(n) if n.name == "humn" => n.number,
+            _ => None,
+        })
+        .ok_or_else(|| eyre!("There is no `humn` monkey with a number!"))
 }
 
 fn solve_inner(nums: BTreeMap<String, i64>, ops: VecDeque<MonkeyInfo>) -> (MonkeyInfo, i64, i64) {
@@ -99,57 +132,64 @@ fn solve_inner(nums: BTreeMap<String, i64>, ops: VecDeque<MonkeyInfo>) -> (Monke
     unimplemented!("What?")
 }
 
+/// The lowest `n` for which `f(n) - r` has the other sign than `f(0) - r`, doubling from `1`.
+fn upper_bound(f: &mut impl FnMut(i64) -> i64, r: i64) -> i64 {
+    let side = (f(0) - r).signum();
+    let mut high = 1;
+
+    while side != 0 && (f(high) - r).signum() == side {
+        high *= 2;
+    }
+
+    high
+}
+
+/// Whether `guess` is still on the `low` side of `r`, for `f` either growing or shrinking from `low` to `high`.
+fn below(guess: i64, r: i64, growing: bool) -> bool {
+    if growing {
+        guess < r
+    } else {
+        guess > r
+    }
+}
+
+/// Finds the lowest `n` in `low..=high` with `f(n) == r`, `f` being monotonic in either direction.
+/// There can be several of them, thanks to the integer divisions.
 fn bisection(mut f: impl FnMut(i64) -> i64, r: i64, low: i64, high: i64) -> i64 {
-    // use num_format::{Locale, ToFormattedString};
-    // println!(
-    //     "Trying to find '{}' with bounds {}-{}",
-    //     r.to_formatted_string(&Locale::en),
-    //     low.to_formatted_string(&Locale::en),
-    //     &high.to_formatted_string(&Locale::en)
-    // );
-    if low == high {
-        return -1;
+    let growing = f(low) < f(high);
+    bisection_rec(&mut f, r, low, high, growing)
+}
+
+fn bisection_rec(f: &mut impl FnMut(i64) -> i64, r: i64, low: i64, high: i64, growing: bool) -> i64 {
+    if low >= high {
+        return low;
     }
 
-    let mid = (high + low) / 2;
-    let guess = f(mid);
-    // let g = &guess.to_formatted_string(&Locale::en);
-    // let m = &mid.to_formatted_string(&Locale::en);
-    // println!("f({}) = {}", m, g);
+    let mid = low + (high - low) / 2;
 
-    match r.cmp(&guess) {
-        Greater => bisection(f, r, low, mid),
-        Less => bisection(f, r, mid, high),
-        Equal => mid - 1,
+    if below(f(mid), r, growing) {
+        bisection_rec(f, r, mid + 1, high, growing)
+    } else {
+        bisection_rec(f, r, low, mid, growing)
     }
 }
 
 fn bisection_imp(mut f: impl FnMut(i64) -> i64, r: i64, low: i64, high: i64) -> i64 {
-    // use num_format::{Locale, ToFormattedString};
-    // println!(
-    //     "Trying to find '{}' with bounds {}-{}",
-    //     r.to_formatted_string(&Locale::en),
-    //     low.to_formatted_string(&Locale::en),
-    //     &high.to_formatted_string(&Locale::en)
-    // );
+    let growing = f(low) < f(high);
     let mut low = low;
     let mut high = high;
 
-    while (high - low).abs() > 1 {
-        let mid = (high + low) / 2;
-        let guess = f(mid);
-        // let g = &guess.to_formatted_string(&Locale::en);
-        // let m = &mid.to_formatted_string(&Locale::en);
-        // println!("f({}) = {}", m, g);
-
-        match r.cmp(&guess) {
-            Greater => high = mid,
-            Less => low = mid,
-            Equal => return mid - 1,
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        if below(f(mid), r, growing) {
+            low = mid + 1;
+        } else {
+            high = mid;
         }
     }
 
-    (low + high) / 2
+    low
 }
 
 fn transform(monkeys: Vec<Monkey>) -> (BTreeMap<String, i64>, VecDeque<MonkeyInfo>) {
@@ -226,62 +266,145 @@ impl Ord for Monkey {
     }
 }
 
-fn parse(i: &str) -> Vec<Monkey> {
+fn parse(i: &str) -> Result<Vec<Monkey>> {
     let mut m = i
         .lines()
-        .map(parse_line)
-        .map(|r| r.unwrap().1)
-        .collect::<Vec<Monkey>>();
+        .map(|l| finish(i, all_consuming(parse_line)(l)))
+        .collect::<Result<Vec<Monkey>>>()?;
     m.sort();
     m.reverse();
-    m
+    Ok(m)
 }
 
-fn parse_line(i: &str) -> IResult<&str, Monkey> {
-    let (val, name) = terminated(alpha1, tag(": "))(i)?;
-
-    if val.len() < 8 {
-        let (_, num) = nom::character::complete::i64(val)?;
-        return Ok((
-            val,
-            Monkey::Number(MonkeyInfo {
-                name: name.to_string(),
-                number: Some(num),
-                ..Default::default()
-            }),
-        ));
-    }
+fn parse_line(i: &str) -> IResult<'_, Monkey> {
+    let (i, name) = terminated(alpha1, token(": "))(i)?;
+
+    let number = map(nom::character::complete::i64, |num| MonkeyInfo {
+        number: Some(num),
+        ..Default::default()
+    });
+    let operation = map(
+        tuple((alpha1, delimited(token(" "), one_of("+-*/"), token(" ")), alpha1)),
+        |(left, op, right): (&str, char, &str)| MonkeyInfo {
+            operation: Some(op),
+            dependents: Some((left.to_string(), right.to_string())),
+            ..Default::default()
+        },
+    );
 
-    let l: Vec<String> = val.split_whitespace().map(|p| p.to_string()).collect();
+    let (i, info) = alt((number, operation))(i)?;
+    let info = MonkeyInfo {
+        name: name.to_string(),
+        ..info
+    };
 
-    return Ok((
-        val,
-        Monkey::Operation(MonkeyInfo {
-            name: name.to_string(),
-            operation: Some(l[1].chars().next().unwrap()),
-            dependents: Some((l[0].clone(), l[2].clone())),
-            ..Default::default()
-        }),
-    ));
+    if info.number.is_some() {
+        Ok((i, Monkey::Number(info)))
+    } else {
+        Ok((i, Monkey::Operation(info)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use aoc_common::{
+        diff::{first_divergence, Divergence},
+        rand::Rng,
+    };
+    use proptest::prelude::*;
+
+    aoc_common::examples!(Solver);
+
+    proptest! {
+        #[test]
+        fn prop_parse_line_roundtrip(name in "[a-z]{4}", number in any::<i64>(), left in "[a-z]{4}", op in "[-+*/]", right in "[a-z]{4}") {
+            let (_, m) = parse_line(&format!("{name}: {number}")).unwrap();
+            prop_assert_eq!(m, Monkey::Number(MonkeyInfo { name: name.clone(), number: Some(number), ..Default::default() }));
+
+            let (_, m) = parse_line(&format!("{name}: {left} {op} {right}")).unwrap();
+            let info = MonkeyInfo {
+                name,
+                operation: op.chars().next(),
+                dependents: Some((left, right)),
+                ..Default::default()
+            };
+            prop_assert_eq!(m, Monkey::Operation(info));
+        }
+
+        #[test]
+        fn prop_parse_no_panic(source in "\\PC*") {
+            let _ = parse(&source);
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Step {
+        /// The number tried and what `f` gave for it.
+        Probe(i64, i64),
+        Answer(i64),
+    }
+
+    /// Runs both bisections, recording every call of `f`, and reports the first one where they went different ways.
+    fn compare_bisections(f: &mut impl FnMut(i64) -> i64, r: i64, low: i64, high: i64) -> Option<Divergence<Step>> {
+        let mut run = |imp: bool| {
+            let mut steps = vec![];
+            let probe = |n| {
+                let v = f(n);
+                steps.push(Step::Probe(n, v));
+                v
+            };
+
+            let answer = if imp { bisection_imp(probe, r, low, high) } else { bisection(probe, r, low, high) };
+            steps.push(Step::Answer(answer));
+            steps
+        };
+
+        let (rec, imp) = (run(false), run(true));
+        first_divergence(rec, imp).map(|d| {
+            let label = format!("call {} of `f`", d.step + 1);
+            d.with_label(label)
+        })
+    }
 
     #[test]
-    fn test_part1() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        let res = solve(&source, 5);
-        let res = res.0.resolve(res.1, res.2);
-        assert_eq!(res, 152);
+    fn test_compare_bisections() {
+        let mut rng = StdRng::seed_from_u64(21);
+
+        for _ in 0..200 {
+            // monotonic in either direction, with plateaus from the division
+            let (a, b, c) = (rng.gen_range(-50..=50), rng.gen_range(-1000..=1000), rng.gen_range(1..=10));
+            let mut f = |n: i64| (a * n + b) / c;
+            let (low, high) = (rng.gen_range(-100..100), rng.gen_range(100..10_000));
+            let r = f(rng.gen_range(low..=high));
+
+            if let Some(d) = compare_bisections(&mut f, r, low, high) {
+                panic!("f(n) = ({a} * n + {b}) / {c}, r = {r}: {d}");
+            }
+        }
+
+        for seed in 0..20 {
+            let source = generate::input(&mut StdRng::seed_from_u64(seed), 100);
+            let (mut f, target) = humn_search(&source).unwrap();
+            let high = upper_bound(&mut f, target);
+
+            if let Some(d) = compare_bisections(&mut f, target, 0, high) {
+                panic!("seed {seed}: {d}");
+            }
+        }
     }
 
     #[test]
-    fn test_part2() {
-        let source = fs::read_to_string("./test_input.txt").unwrap();
-        assert_eq!(part2(&source).unwrap(), "301");
+    fn test_bisection() {
+        // several inputs give the same output, the lowest one is the answer
+        let mut shrinking = |n: i64| (100 - n) / 3;
+        let high = upper_bound(&mut shrinking, 10);
+        assert_eq!(bisection(shrinking, 10, 0, high), 68);
+        assert_eq!(bisection_imp(shrinking, 10, 0, high), 68);
+
+        let growing = |n: i64| n / 4;
+        assert_eq!(bisection(growing, 10, 0, 1000), 40);
+        assert_eq!(bisection_imp(growing, 10, 0, 1000), 40);
     }
 
     #[test]
@@ -290,7 +413,7 @@ mod tests {
         let m1 = parse_line("aaaa: 4").unwrap().1;
         let m2 = parse_line("bbbb: aaaa + cccc").unwrap().1;
         assert!(m1 > m2);
-        let mut v = vec![&m1, &m2];
+        let mut v = [&m1, &m2];
         v.sort();
         v.reverse();
         assert_eq!(v[0], &m1);
@@ -299,8 +422,11 @@ mod tests {
     #[test]
     fn test_parse() {
         let i = "sjmn: drzm * dbpl\nsllz: 4";
-        let r = parse(i);
+        let r = parse(i).unwrap();
         assert_eq!(r.len(), 2);
+
+        let e = parse("sjmn: drzm * dbpl\nsllz: drzm % 4").unwrap_err();
+        assert_eq!(e.to_string(), "Expected one of the allowed characters at line 2, column 12");
     }
 
     #[test]
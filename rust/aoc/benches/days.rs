@@ -0,0 +1,57 @@
+use std::env;
+
+use aoc::{bench, inputs::Inputs, DAYS};
+use criterion::{black_box, Criterion};
+
+/// Every day gets its own group, with parsing (where the day has a separate step) and both parts measured on their own.
+/// Filter like any criterion bench, e.g. `cargo bench -p aoc -- 2022/day-20`.
+fn bench_days(c: &mut Criterion) {
+    let inputs = Inputs::from_env(None);
+    for d in DAYS {
+        let source = inputs.read(d.year, d.day).unwrap();
+        let params = d.params().unwrap();
+        let mut group = c.benchmark_group(format!("{}/day-{:02}", d.year, d.day));
+
+        if let Some(Ok(())) = d.solution.parse(&source) {
+            group.bench_function("parse", |b| b.iter(|| d.solution.parse(black_box(&source))));
+        }
+
+        for part in [1, 2] {
+            // unfinished parts have nothing worth measuring
+            if d.solve(part, &source, &params).map_or(true, |a| a.is_empty()) {
+                continue;
+            }
+
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter(|| d.solution.part_with(part, black_box(&source), &params))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+/// The `--baseline <name>` criterion was asked to compare against, if any.
+fn baseline() -> Option<String> {
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--baseline" {
+            return args.next();
+        }
+        if let Some(name) = arg.strip_prefix("--baseline=") {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+fn main() {
+    let mut c = Criterion::default().configure_from_args();
+    bench_days(&mut c);
+    c.final_summary();
+
+    let dir = bench::criterion_dir();
+    let measurements = bench::collect(&dir, baseline().as_deref()).unwrap();
+    let out = bench::write_report(&dir, &measurements).unwrap();
+    println!("Report written to {}", out.display());
+}
@@ -0,0 +1,97 @@
+//! The runner's global allocator, the system one plus a per-thread count of the bytes in use,
+//! so that [`measure`] can tell the peak memory of a solution even while others run on the pool.
+//!
+//! Only the runner binary installs it, the benches keep the plain system allocator. Without it [`measure`] gives 0.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+pub struct Counting;
+
+/// For the tests of the library, which measure too.
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+thread_local! {
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+fn grow(size: usize) {
+    // the thread locals are gone while a thread shuts down, its allocations are not interesting by then
+    let _ = CURRENT.try_with(|c| {
+        let current = c.get() + size;
+        c.set(current);
+        let _ = PEAK.try_with(|p| p.set(p.get().max(current)));
+    });
+}
+
+fn shrink(size: usize) {
+    // memory allocated by another thread can be freed on this one
+    let _ = CURRENT.try_with(|c| c.set(c.get().saturating_sub(size)));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new
+    }
+}
+
+/// Runs `f` and returns the most bytes it had allocated at once, on top of what the thread held before.
+/// Only allocations on the calling thread count.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let base = CURRENT.with(Cell::get);
+    PEAK.with(|p| p.set(base));
+
+    let res = f();
+
+    (res, PEAK.with(Cell::get) - base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (len, peak) = measure(|| {
+            let v = vec![0u8; 1 << 20];
+            drop(v);
+            vec![0u8; 1000].len()
+        });
+        assert_eq!(len, 1000);
+        assert!((1 << 20..1 << 21).contains(&peak), "{peak}");
+
+        let (_, peak) = measure(|| 1 + 1);
+        assert_eq!(peak, 0);
+    }
+}
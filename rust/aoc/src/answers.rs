@@ -0,0 +1,103 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::repo_root;
+
+/// Known-good answers of a single year, checked in as `<year>/answers.toml`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, Recorded>);
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Recorded {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+impl Answers {
+    pub fn path(year: u16) -> PathBuf {
+        repo_root().join(year.to_string()).join("answers.toml")
+    }
+
+    /// Loads the answers of `year`, a missing file simply means nothing was recorded yet.
+    pub fn load(year: u16) -> Result<Self> {
+        let path = Self::path(year);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(source: &str) -> Result<Self> {
+        Ok(toml::from_str(source)?)
+    }
+
+    pub fn save(&self, year: u16) -> Result<()> {
+        fs::write(Self::path(year), toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        let recorded = self.0.get(&key(day))?;
+        match part {
+            1 => recorded.part1.as_deref(),
+            2 => recorded.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: String) -> Result<()> {
+        if !(1..=2).contains(&part) {
+            return Err(eyre!("There is no part {part}!"));
+        }
+
+        let recorded = self.0.entry(key(day)).or_default();
+        match part {
+            1 => recorded.part1 = Some(answer),
+            _ => recorded.part2 = Some(answer),
+        }
+        Ok(())
+    }
+}
+
+fn key(day: u8) -> String {
+    format!("day-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut a = Answers::default();
+        a.set(1, 1, "24000".to_string()).unwrap();
+        a.set(10, 2, "#..#\n.##.".to_string()).unwrap();
+        assert_eq!(
+            a.set(1, 3, "1".to_string()).unwrap_err().to_string(),
+            "There is no part 3!"
+        );
+
+        let s = toml::to_string(&a).unwrap();
+        assert!(s.contains("[day-01]"));
+
+        let b = Answers::parse(&s).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(b.get(1, 1), Some("24000"));
+        assert_eq!(b.get(1, 2), None);
+        assert_eq!(b.get(10, 2), Some("#..#\n.##."));
+        assert_eq!(b.get(2, 1), None);
+    }
+
+    #[test]
+    fn test_recorded_years_parse() {
+        for year in [2019, 2020, 2022] {
+            assert!(Answers::load(year).is_ok());
+        }
+    }
+}
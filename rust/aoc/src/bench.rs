@@ -0,0 +1,171 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::repo_root;
+
+/// One benchmark from the criterion output, times are the mean in nanoseconds.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Measurement {
+    pub id: String,
+    pub mean_ns: f64,
+    pub baseline_ns: Option<f64>,
+}
+
+impl Measurement {
+    /// Relative change against the baseline, `0.1` meaning 10% slower.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline_ns.map(|b| (self.mean_ns - b) / b)
+    }
+}
+
+#[derive(Deserialize)]
+struct BenchmarkId {
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+/// Where criterion keeps its results, resolved the same way criterion does it.
+pub fn criterion_dir() -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return home.into();
+    }
+
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| repo_root().join("target"))
+        .join("criterion")
+}
+
+/// Collects the latest run of every benchmark under `dir`, along with the saved `baseline` if asked for.
+/// The slowest benchmarks come first.
+pub fn collect(dir: &Path, baseline: Option<&str>) -> Result<Vec<Measurement>> {
+    let mut res = Vec::new();
+    visit(dir, baseline, &mut res)?;
+
+    res.sort_by(|a, b| b.mean_ns.total_cmp(&a.mean_ns));
+    Ok(res)
+}
+
+fn visit(dir: &Path, baseline: Option<&str>, res: &mut Vec<Measurement>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let latest = dir.join("new");
+    if latest.join("benchmark.json").exists() {
+        let id: BenchmarkId = serde_json::from_str(&fs::read_to_string(latest.join("benchmark.json"))?)?;
+        let baseline_ns = match baseline {
+            Some(b) if dir.join(b).join("estimates.json").exists() => Some(mean(&dir.join(b))?),
+            _ => None,
+        };
+
+        res.push(Measurement {
+            id: id.full_id,
+            mean_ns: mean(&latest)?,
+            baseline_ns,
+        });
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        visit(&entry?.path(), baseline, res)?;
+    }
+
+    Ok(())
+}
+
+fn mean(dir: &Path) -> Result<f64> {
+    let estimates: Estimates = serde_json::from_str(&fs::read_to_string(dir.join("estimates.json"))?)?;
+    Ok(estimates.mean.point_estimate)
+}
+
+pub fn to_json(measurements: &[Measurement]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(measurements)?)
+}
+
+pub fn to_csv(measurements: &[Measurement]) -> String {
+    let mut res = String::from("id,mean_ns,baseline_ns,change\n");
+    for m in measurements {
+        let baseline = m.baseline_ns.map(|b| b.to_string()).unwrap_or_default();
+        let change = m.change().map(|c| format!("{c:.4}")).unwrap_or_default();
+        res.push_str(&format!("{},{},{baseline},{change}\n", m.id, m.mean_ns));
+    }
+    res
+}
+
+/// Writes `report.json` and `report.csv` next to criterion's own output and returns the directory.
+pub fn write_report(dir: &Path, measurements: &[Measurement]) -> Result<PathBuf> {
+    let out = dir.join("aoc");
+    fs::create_dir_all(&out)?;
+
+    fs::write(out.join("report.json"), to_json(measurements)?)?;
+    fs::write(out.join("report.csv"), to_csv(measurements))?;
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_bench(dir: &Path, sub: &str, full_id: &str, mean: f64) {
+        let d = dir.join(full_id.replace('/', "_")).join(sub);
+        fs::create_dir_all(&d).unwrap();
+        fs::write(
+            d.join("benchmark.json"),
+            format!(r#"{{"group_id":"x","full_id":"{full_id}"}}"#),
+        )
+        .unwrap();
+        fs::write(
+            d.join("estimates.json"),
+            format!(r#"{{"mean":{{"point_estimate":{mean},"standard_error":1.0}}}}"#),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_collect() {
+        let dir = env::temp_dir().join(format!("aoc-bench-{}", std::process::id()));
+        fake_bench(&dir, "new", "2022/day-20/part1", 300.0);
+        fake_bench(&dir, "main", "2022/day-20/part1", 200.0);
+        fake_bench(&dir, "new", "2022/day-20/parse", 400.0);
+
+        let res = collect(&dir, Some("main")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            res,
+            vec![
+                Measurement {
+                    id: "2022/day-20/parse".to_string(),
+                    mean_ns: 400.0,
+                    baseline_ns: None
+                },
+                Measurement {
+                    id: "2022/day-20/part1".to_string(),
+                    mean_ns: 300.0,
+                    baseline_ns: Some(200.0)
+                },
+            ]
+        );
+        assert_eq!(res[1].change(), Some(0.5));
+        assert_eq!(
+            to_csv(&res),
+            "id,mean_ns,baseline_ns,change\n2022/day-20/parse,400,,\n2022/day-20/part1,300,200,0.5000\n"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+macro_rules! day {
+    ($year:literal, $day:literal, $krate:ident) => {
+        Day {
+            year: $year,
+            day: $day,
+            solution: &$krate::Solver,
+        }
+    };
+}
+
+/// Every solved day, in calendar order.
+pub static DAYS: &[Day] = &[
+    day!(2019, 1, aoc_2019_day_01),
+    day!(2019, 2, aoc_2019_day_02),
+    day!(2019, 3, aoc_2019_day_03),
+    day!(2019, 4, aoc_2019_day_04),
+    day!(2019, 5, aoc_2019_day_05),
+    day!(2020, 1, aoc_2020_day_01),
+    day!(2022, 1, aoc_2022_day_01),
+    day!(2022, 2, aoc_2022_day_02),
+    day!(2022, 3, aoc_2022_day_03),
+    day!(2022, 4, aoc_2022_day_04),
+    day!(2022, 5, aoc_2022_day_05),
+    day!(2022, 6, aoc_2022_day_06),
+    day!(2022, 7, aoc_2022_day_07),
+    day!(2022, 8, aoc_2022_day_08),
+    day!(2022, 9, aoc_2022_day_09),
+    day!(2022, 10, aoc_2022_day_10),
+    day!(2022, 11, aoc_2022_day_11),
+    day!(2022, 12, aoc_2022_day_12),
+    day!(2022, 13, aoc_2022_day_13),
+    day!(2022, 14, aoc_2022_day_14),
+    day!(2022, 15, aoc_2022_day_15),
+    day!(2022, 16, aoc_2022_day_16),
+    day!(2022, 20, aoc_2022_day_20),
+    day!(2022, 21, aoc_2022_day_21),
+];
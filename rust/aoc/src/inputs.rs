@@ -0,0 +1,311 @@
+//! Where the real puzzle inputs come from: the day's `real_input.txt` when it has one, otherwise a local cache
+//! that missing inputs are downloaded into, with the session token of a logged in browser.
+//!
+//! The inputs are personal and not to be published, so the cache refuses to write anywhere git would commit.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use color_eyre::{eyre::eyre, Result};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// The puzzle site asks automated tools to say who they are.
+const USER_AGENT: &str = "github.com/phate45/advent_of_code input fetcher";
+
+/// What the inputs are downloaded with, so that the tests can talk to a local server instead.
+pub trait Http {
+    /// The status code and the body of a GET request.
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String)>;
+}
+
+pub struct Ureq;
+
+impl Http for Ureq {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String)> {
+        let request = headers
+            .iter()
+            .fold(ureq::get(url), |r, (name, value)| r.set(name, value));
+
+        match request.call() {
+            Ok(response) => Ok((response.status(), response.into_string()?)),
+            Err(ureq::Error::Status(status, response)) => Ok((status, response.into_string().unwrap_or_default())),
+            Err(e) => Err(eyre!("Cannot reach {url}: {e}")),
+        }
+    }
+}
+
+pub struct Inputs {
+    pub dir: PathBuf,
+    pub session: Option<String>,
+    pub base_url: String,
+    http: Box<dyn Http>,
+}
+
+impl Inputs {
+    pub fn new(dir: PathBuf, http: Box<dyn Http>) -> Self {
+        Self {
+            dir,
+            session: None,
+            base_url: BASE_URL.to_string(),
+            http,
+        }
+    }
+
+    /// The cache in `dir`, or `AOC_CACHE_DIR`, or `.inputs` at the repository root.
+    /// The session token is `AOC_SESSION`, or the contents of the `session` file in the cache.
+    pub fn from_env(dir: Option<PathBuf>) -> Self {
+        let dir = dir
+            .or_else(|| env::var_os("AOC_CACHE_DIR").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(".inputs"));
+        let dir = crate::repo_root().join(dir);
+
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| fs::read_to_string(dir.join("session")).ok())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+
+        Self {
+            session,
+            ..Self::new(dir, Box::new(Ureq))
+        }
+    }
+
+    pub fn cached(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day-{day:02}.txt"))
+    }
+
+    /// The input of a day: its own `real_input.txt` if that is not empty, otherwise the cached one,
+    /// which is downloaded first if needed.
+    pub fn resolve(&self, year: u16, day: u8) -> Result<PathBuf> {
+        if let Some(d) = crate::find(year, day) {
+            let path = d.input_path();
+            if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+                return Ok(path);
+            }
+        }
+
+        self.fetch(year, day)
+    }
+
+    /// The contents of the input [`resolve`](Self::resolve) finds.
+    pub fn read(&self, year: u16, day: u8) -> Result<String> {
+        Ok(fs::read_to_string(self.resolve(year, day)?)?)
+    }
+
+    /// The cached input of a day, downloaded unless it already is in the cache.
+    pub fn fetch(&self, year: u16, day: u8) -> Result<PathBuf> {
+        let path = self.cached(year, day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let session = self.session.as_deref().ok_or_else(|| {
+            eyre!(
+                "The input of {year} day {day} is not cached and there is no session token to download it, \
+                 set AOC_SESSION or put the token into {}!",
+                self.dir.join("session").display()
+            )
+        })?;
+        self.check_ignored()?;
+
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let cookie = format!("session={session}");
+        let (status, body) = self
+            .http
+            .get(&url, &[("Cookie", &cookie), ("User-Agent", USER_AGENT)])?;
+        match status {
+            200 if !body.is_empty() => {}
+            200 => return Err(eyre!("{url} sent an empty input!")),
+            404 => return Err(eyre!("The input of {year} day {day} is not available (yet)!")),
+            400 | 401 | 500 => {
+                return Err(eyre!(
+                    "{url} rejected the session token ({status}), it may have expired!"
+                ))
+            }
+            _ => {
+                return Err(eyre!(
+                    "{url} failed with {status}: {}",
+                    body.lines().next().unwrap_or_default()
+                ))
+            }
+        }
+
+        // written whole or not at all, a half written input would be taken for a cached one
+        fs::create_dir_all(path.parent().unwrap())?;
+        let partial = path.with_extension("part");
+        fs::write(&partial, body)?;
+        fs::rename(partial, &path)?;
+
+        Ok(path)
+    }
+
+    /// Fails when the cache is inside the repository and git would commit it, or already has.
+    pub fn check_ignored(&self) -> Result<()> {
+        check_ignored(&crate::repo_root(), &self.dir)
+    }
+}
+
+fn check_ignored(repo: &Path, dir: &Path) -> Result<()> {
+    if !dir.starts_with(repo) {
+        return Ok(());
+    }
+
+    // without git there is nothing to commit the inputs with
+    let Ok(ignored) = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["check-ignore", "-q"])
+        .arg(dir)
+        .status()
+    else {
+        return Ok(());
+    };
+    if ignored.code() == Some(1) {
+        return Err(eyre!(
+            "The input cache {} is not ignored by git, add it to .gitignore!",
+            dir.display()
+        ));
+    }
+
+    let tracked = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["ls-files", "--"])
+        .arg(dir)
+        .output()?;
+    if !tracked.stdout.is_empty() {
+        return Err(eyre!(
+            "Inputs in {} are committed to git, remove them with `git rm --cached`!",
+            dir.display()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    /// The url and the cookie of every request.
+    type Requests = Arc<Mutex<Vec<(String, String)>>>;
+
+    /// A stand-in for the puzzle site on a local port, answering `answers` requests and keeping what was asked.
+    fn mock_site(answers: usize) -> (String, Requests) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let seen = Arc::new(Mutex::new(vec![]));
+
+        let log = seen.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests().take(answers) {
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string())
+                    .unwrap_or_default();
+                log.lock().unwrap().push((request.url().to_string(), cookie.clone()));
+
+                let response = match (request.url(), cookie.as_str()) {
+                    (_, c) if c != "session=secret" => {
+                        tiny_http::Response::from_string("Please log in").with_status_code(400)
+                    }
+                    ("/2022/day/1/input", _) => tiny_http::Response::from_string("1000\n2000\n"),
+                    _ => tiny_http::Response::from_string("Not found").with_status_code(404),
+                };
+                request.respond(response).unwrap();
+            }
+        });
+
+        (url, seen)
+    }
+
+    fn inputs(name: &str, url: &str, session: Option<&str>) -> Inputs {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        Inputs {
+            session: session.map(str::to_string),
+            base_url: url.to_string(),
+            ..Inputs::new(dir, Box::new(Ureq))
+        }
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (url, seen) = mock_site(2);
+        let inputs = inputs("fetch", &url, Some("secret"));
+
+        let path = inputs.fetch(2022, 1).unwrap();
+        assert_eq!(path, inputs.dir.join("2022/day-01.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        // cached now
+        inputs.fetch(2022, 1).unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![("/2022/day/1/input".to_string(), "session=secret".to_string())]
+        );
+
+        let e = inputs.fetch(2022, 2).unwrap_err();
+        assert_eq!(e.to_string(), "The input of 2022 day 2 is not available (yet)!");
+        assert!(!inputs.cached(2022, 2).exists());
+
+        fs::remove_dir_all(&inputs.dir).unwrap();
+    }
+
+    #[test]
+    fn test_session() {
+        let (url, _) = mock_site(1);
+
+        let e = inputs("none", &url, None).fetch(2022, 1).unwrap_err();
+        assert!(e.to_string().contains("there is no session token"), "{e}");
+
+        let e = inputs("expired", &url, Some("old")).fetch(2022, 1).unwrap_err();
+        assert!(
+            e.to_string()
+                .ends_with("rejected the session token (400), it may have expired!"),
+            "{e}"
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        // a day with its own input never goes to the site
+        let inputs = inputs("resolve", "http://127.0.0.1:9", None);
+        assert_eq!(
+            inputs.resolve(2022, 14).unwrap(),
+            crate::find(2022, 14).unwrap().input_path()
+        );
+    }
+
+    #[test]
+    fn test_check_ignored() {
+        let repo = crate::repo_root();
+        // a source tarball has no git to ask
+        if !repo.join(".git").exists() {
+            return;
+        }
+
+        check_ignored(&repo, &repo.join(".inputs")).unwrap();
+        check_ignored(&repo, &env::temp_dir()).unwrap();
+
+        let e = check_ignored(&repo, &repo.join("rust/inputs")).unwrap_err();
+        assert!(
+            e.to_string().ends_with("is not ignored by git, add it to .gitignore!"),
+            "{e}"
+        );
+        // the inputs of the days are committed, a cache there would be too
+        assert!(check_ignored(&repo, &repo.join("2022/rust/day-01")).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+mod days;
+pub mod inputs;
+pub mod memo;
+pub mod regression;
+pub mod run_all;
+pub mod scaffold;
+pub mod serve;
+pub mod status;
+pub mod visualize;
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{eyre::eyre, Answer, Params, Result, Solution};
+
+pub use days::DAYS;
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solution: &'static dyn Solution,
+}
+
+impl Day {
+    /// Runs one part, turning a panic inside the solution into an error.
+    pub fn solve(&self, part: u8, input: &str, params: &Params) -> Result<Answer> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.solution.part_with(part, input, params))).unwrap_or_else(
+            |payload| {
+                Err(eyre!(
+                    "{} day {} part {part} panicked: {}",
+                    self.year,
+                    self.day,
+                    panic_message(&payload)
+                ))
+            },
+        )
+    }
+
+    /// Directory of the day's crate, relative to the repository root.
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(format!("{}/rust/day-{:02}", self.year, self.day))
+    }
+
+    /// The puzzle input the per-day bins read, `real_input.txt` in the crate directory.
+    pub fn input_path(&self) -> PathBuf {
+        repo_root().join(self.dir()).join("real_input.txt")
+    }
+
+    /// The parameters of the real input, from the day's `params.toml`.
+    pub fn params(&self) -> Result<Params> {
+        self.params_for(&self.input_path())
+    }
+
+    /// The parameters of any `input` to the day, the real input's unless it is one of the day's examples.
+    pub fn params_for(&self, input: &Path) -> Result<Params> {
+        Params::load(&repo_root().join(self.dir()), input)
+    }
+}
+
+/// A duration for people, in whichever unit keeps it readable.
+pub fn duration(ns: f64) -> String {
+    match ns {
+        n if n >= 1e9 => format!("{:.2} s", n / 1e9),
+        n if n >= 1e6 => format!("{:.2} ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.2} µs", n / 1e3),
+        n => format!("{n:.0} ns"),
+    }
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+pub fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("The runner lives two levels below the repository root!")
+        .to_path_buf()
+}
+
+pub(crate) fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let d = find(2022, 14).unwrap();
+        assert_eq!(d.dir(), PathBuf::from("2022/rust/day-14"));
+
+        assert!(find(2022, 17).is_none());
+    }
+
+    #[test]
+    fn test_days_unique() {
+        for (i, a) in DAYS.iter().enumerate() {
+            for b in &DAYS[i + 1..] {
+                assert!((a.year, a.day) != (b.year, b.day));
+            }
+        }
+    }
+
+    #[test]
+    fn test_inputs_exist() {
+        for d in DAYS {
+            assert!(d.input_path().exists(), "{:?}", d.input_path());
+        }
+    }
+
+    #[test]
+    fn test_params() {
+        let d = find(2019, 2).unwrap();
+        let real = Params::new().with("noun", 12).with("verb", 2);
+        assert_eq!(d.params().unwrap(), real);
+        assert_eq!(d.params_for(&std::env::temp_dir().join("x.txt")).unwrap(), real);
+        assert_eq!(d.params_for(Path::new("input.txt")).unwrap(), real);
+
+        let example = repo_root().join(d.dir()).join("test_input.txt");
+        assert_eq!(
+            d.params_for(&example).unwrap(),
+            Params::new().with("noun", 9).with("verb", 10)
+        );
+    }
+
+    #[test]
+    fn test_run_example() {
+        let d = find(2022, 1).unwrap();
+        let source = std::fs::read_to_string(repo_root().join(d.dir()).join("test_input.txt")).unwrap();
+        assert_eq!(d.solution.part1(&source).unwrap(), Answer::Number(24000));
+        assert_eq!(d.solution.part(2, &source).unwrap(), Answer::Number(45000));
+        assert!(d.solution.part(3, &source).is_err());
+    }
+}
@@ -0,0 +1,546 @@
+use std::{
+    fs,
+    io::{self, Read},
+    panic,
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    alloc::Counting,
+    answers::Answers,
+    bench, duration,
+    inputs::Inputs,
+    memo::{self, Entry, Key, Memo},
+    regression::{self, Status},
+    run_all, status,
+    visualize::Backend,
+    Day,
+};
+use aoc_common::{Answer, Params};
+use clap::{Parser, Subcommand, ValueEnum};
+use color_eyre::{eyre::eyre, Result};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code solutions of every year")]
+struct Cli {
+    /// Show what the solutions log, more `-v`s show more; `RUST_LOG` takes precedence
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Where downloaded inputs are cached; `AOC_CACHE_DIR` or `.inputs` by default
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one or both parts of a single day
+    Run {
+        year: u16,
+        day: u8,
+        /// Only run this part (1 or 2)
+        #[arg(short, long)]
+        part: Option<u8>,
+        /// Puzzle input file, `-` for stdin; defaults to the day's `real_input.txt`, or the cached or downloaded input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Set a puzzle parameter, overriding the day's `params.toml`
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<Params>,
+        /// Watch the simulation, on days that have one
+        #[arg(long, value_enum)]
+        visualize: Option<Backend>,
+        /// Where `--visualize` writes its files
+        #[arg(long, default_value = "target/visualize")]
+        out: PathBuf,
+        /// Milliseconds between the frames of `--visualize`
+        #[arg(long, default_value_t = 100)]
+        delay: u64,
+        /// Compute the answers even when the same code already did on the same input
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show every time a day was computed by `run`, how long it took and what it answered
+    History { year: u16, day: u8 },
+    /// Run every part of every day on a thread pool and list them by how long they took
+    RunAll {
+        /// Only run this year
+        year: Option<u16>,
+        /// How many parts run at once; one per core by default
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+    /// Show which days are solved, stubbed, borrowed or missing, as a calendar here and in an HTML file
+    Status {
+        /// Only show this year
+        year: Option<u16>,
+        /// Where to write the HTML calendar
+        #[arg(long, default_value = "target/status.html")]
+        html: PathBuf,
+    },
+    /// Serve a local web page to run any day on a pasted input and watch its frames
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: String,
+    },
+    /// Print a random but valid input of a day, e.g. to pipe into `run --input -`
+    Generate {
+        year: u16,
+        day: u8,
+        /// How big the input is, what it counts (lines, monkeys, packets...) depends on the day
+        #[arg(short, long, default_value_t = 100)]
+        size: usize,
+        /// The same seed gives the same input; random by default
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Download the inputs of a year, or of one day, into the cache; needs the session token in `AOC_SESSION`
+    Fetch { year: u16, day: Option<u8> },
+    /// List every available year and day
+    List,
+    /// Re-run every day on its real input and compare with the recorded answers
+    Regression {
+        /// Only check this year
+        year: Option<u16>,
+    },
+    /// Save the current answers of a day as known-good in `<year>/answers.toml`
+    Record {
+        year: u16,
+        day: u8,
+        /// Only record this part (1 or 2)
+        #[arg(short, long)]
+        part: Option<u8>,
+    },
+    /// Generate the crate of a new day and register it with the workspace and this runner
+    New {
+        year: u16,
+        day: u8,
+        /// Start with a nom `parser.rs` module
+        #[arg(long)]
+        parser: bool,
+    },
+    /// Show the results of the last `cargo bench -p aoc` run, slowest first
+    Bench {
+        /// Compare against a baseline saved with `cargo bench -p aoc -- --save-baseline <name>`
+        #[arg(short, long)]
+        baseline: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+fn main() -> Result<ExitCode> {
+    color_eyre::install()?;
+
+    let cli = Cli::parse();
+    aoc_common::log::init(cli.verbose);
+
+    match cli.command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            params,
+            visualize,
+            out,
+            delay,
+            force,
+        } => {
+            let watch = visualize.map(|v| (v, out, Duration::from_millis(delay)));
+            let inputs = Inputs::from_env(cli.cache_dir);
+            run(year, day, part, input, params, Mode { watch, force }, &inputs)?
+        }
+        Command::History { year, day } => history(year, day)?,
+        Command::RunAll { year, jobs } => return run_all(year, jobs, &Inputs::from_env(cli.cache_dir)),
+        Command::Status { year, html } => status(year, html, &Inputs::from_env(cli.cache_dir))?,
+        Command::Serve { addr } => aoc::serve::serve(&addr, &Inputs::from_env(cli.cache_dir))?,
+        Command::Generate { year, day, size, seed } => generate(year, day, size, seed)?,
+        Command::Fetch { year, day } => {
+            let inputs = Inputs::from_env(cli.cache_dir);
+            for day in day.map_or(1..=25, |d| d..=d) {
+                println!("{}", inputs.fetch(year, day)?.display());
+            }
+        }
+        Command::List => {
+            for d in aoc::DAYS {
+                println!("{} day {:02}  {}", d.year, d.day, d.dir().display());
+            }
+        }
+        Command::Regression { year } => return regression(year, &Inputs::from_env(cli.cache_dir)),
+        Command::Record { year, day, part } => record(year, day, part, &Inputs::from_env(cli.cache_dir))?,
+        Command::New { year, day, parser } => {
+            let dir = aoc::scaffold::create(year, day, parser)?;
+            println!("Created {}, put the example into its test_input.txt", dir.display());
+        }
+        Command::Bench { baseline, format } => bench_report(baseline, format)?,
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    }
+}
+
+/// [`Day::solve`], with a panic only reported by the error it turns into.
+fn solve(d: &Day, part: u8, source: &str, params: &Params) -> Result<Answer> {
+    quietly(|| d.solve(part, source, params))
+}
+
+/// Runs `f` without printing its panics, for callers that turn them into errors, then puts the previous hook back.
+fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let res = f();
+    panic::set_hook(hook);
+    res
+}
+
+/// How `run` gets the answers.
+struct Mode {
+    /// Record the frames with the backend into the directory, this long apart.
+    watch: Option<(Backend, PathBuf, Duration)>,
+    /// Compute the answers even if the memo has them.
+    force: bool,
+}
+
+fn run(
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    overrides: Vec<Params>,
+    mode: Mode,
+    inputs: &Inputs,
+) -> Result<()> {
+    let d = aoc::find(year, day).ok_or_else(|| eyre!("There is no solution for {year} day {day}!"))?;
+
+    // stdin most likely carries the real input
+    let (input, mut params) = match input {
+        Some(i) if i.as_os_str() != "-" => {
+            let params = d.params_for(&i)?;
+            (i, params)
+        }
+        Some(i) => (i, d.params()?),
+        None => (inputs.resolve(year, day)?, d.params()?),
+    };
+    overrides.into_iter().for_each(|o| params.merge(o));
+
+    let source = read_input(input)?;
+    let mut memo = Memo::load(&Memo::default_path())?;
+    let code = memo::source_hash(&aoc::repo_root().join(d.dir()))?;
+
+    for p in parts(part) {
+        let answer = match &mode.watch {
+            Some((backend, out, delay)) => {
+                let path = backend.path(&aoc::repo_root().join(out), year, day, p);
+                let mut recorder = backend.recorder(path.clone(), *delay);
+                match d.solution.visualize_with(p, &source, &params, &mut *recorder) {
+                    Some(answer) => {
+                        recorder.finish()?;
+                        if let Some(path) = path {
+                            println!("Wrote {}", path.display());
+                        }
+                        answer?
+                    }
+                    // the other part may still be worth watching
+                    None if part.is_none() => {
+                        println!("There is nothing to watch in part {p}");
+                        solve(d, p, &source, &params)?
+                    }
+                    None => return Err(eyre!("There is nothing to watch in {year} day {day} part {p}!")),
+                }
+            }
+            None => {
+                let key = Key::new(year, day, p, &source, &params, &code);
+                match memo.get(&key) {
+                    Some(e) if !mode.force => {
+                        let time = duration(e.time_ns as f64);
+                        eprintln!(
+                            "{year} day {day:02} part {p} is cached, it took {time} on {}",
+                            memo::date(e.at)
+                        );
+                        Answer::from(e.answer.clone())
+                    }
+                    _ => {
+                        let start = Instant::now();
+                        let answer = solve(d, p, &source, &params)?;
+                        memo.add(Entry::new(key, answer.to_string(), start.elapsed()))?;
+                        answer
+                    }
+                }
+            }
+        };
+
+        if let Answer::Art(_) = answer {
+            println!("{year} day {day:02} part {p}:\n{answer}");
+        } else {
+            println!("{year} day {day:02} part {p}: {answer}");
+        }
+    }
+
+    Ok(())
+}
+
+fn run_all(year: Option<u16>, jobs: Option<usize>, inputs: &Inputs) -> Result<ExitCode> {
+    let start = Instant::now();
+    let runs = quietly(|| {
+        run_all::run(
+            aoc::DAYS.iter().filter(|d| year.is_none_or(|y| d.year == y)),
+            jobs,
+            inputs,
+        )
+    })?;
+    let wall = start.elapsed();
+
+    // the bar is the share of the slowest part, so the brute force stands out
+    let slowest = runs.first().map_or(0.0, |r| r.time.as_secs_f64()).max(f64::EPSILON);
+    println!(
+        "{:<4}  {:>3}  {:>4}  {:<20}  {:>10}  {:>10}  cost",
+        "year", "day", "part", "answer", "time", "peak"
+    );
+    for r in &runs {
+        let answer = r
+            .error
+            .as_deref()
+            .map(|e| format!("ERROR {e}"))
+            .or(r.answer.clone())
+            .unwrap_or_default();
+        let bar = "#".repeat((r.time.as_secs_f64() / slowest * 20.0).round() as usize);
+        println!(
+            "{:<4}  {:>3}  {:>4}  {:<20}  {:>10}  {:>10}  {bar}",
+            r.year,
+            r.day,
+            r.part,
+            short(&answer, 20),
+            duration(r.time.as_nanos() as f64),
+            bytes(r.peak)
+        );
+    }
+
+    let work: f64 = runs.iter().map(|r| r.time.as_nanos() as f64).sum();
+    let errors = runs.iter().filter(|r| r.error.is_some()).count();
+    println!();
+    println!(
+        "{} parts in {} ({} of work), {errors} errors",
+        runs.len(),
+        duration(wall.as_nanos() as f64),
+        duration(work)
+    );
+
+    if errors > 0 {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn status(year: Option<u16>, html: PathBuf, inputs: &Inputs) -> Result<()> {
+    let mut years: Vec<u16> = aoc::DAYS
+        .iter()
+        .map(|d| d.year)
+        .filter(|y| year.is_none_or(|year| year == *y))
+        .collect();
+    years.dedup();
+    if years.is_empty() {
+        return Err(eyre!("There are no solutions for {}!", year.unwrap_or_default()));
+    }
+
+    let statuses = quietly(|| status::collect(&years, inputs))?;
+
+    print!("{}", status::calendar(&statuses));
+
+    let html = aoc::repo_root().join(html);
+    if let Some(dir) = html.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&html, status::html(&statuses))?;
+    println!("\nWrote {}", html.display());
+
+    Ok(())
+}
+
+fn history(year: u16, day: u8) -> Result<()> {
+    let memo = Memo::load(&Memo::default_path())?;
+    let entries: Vec<_> = memo.history(year, day).collect();
+    if entries.is_empty() {
+        return Err(eyre!("{year} day {day} has not been run yet!"));
+    }
+
+    // the short hashes tell apart the versions of the code and the inputs
+    println!(
+        "{:<16}  {:>4}  {:>10}  {:<8}  {:<8}  answer",
+        "when", "part", "time", "code", "input"
+    );
+    for e in entries {
+        println!(
+            "{:<16}  {:>4}  {:>10}  {:<8}  {:<8}  {}",
+            memo::date(e.at),
+            e.part,
+            duration(e.time_ns as f64),
+            e.source.get(..8).unwrap_or(&e.source),
+            e.input.get(..8).unwrap_or(&e.input),
+            short(&e.answer, 40)
+        );
+    }
+
+    Ok(())
+}
+
+fn generate(year: u16, day: u8, size: usize, seed: Option<u64>) -> Result<()> {
+    let d = aoc::find(year, day).ok_or_else(|| eyre!("There is no solution for {year} day {day}!"))?;
+    let random = seed.is_none();
+    let seed = seed.unwrap_or_else(aoc_common::rand::random);
+
+    let input = d
+        .solution
+        .generate(seed, size)
+        .ok_or_else(|| eyre!("There is no input generator for {year} day {day}!"))?;
+    if random {
+        // stdout is the input, so that a run worth keeping can be reproduced
+        eprintln!("seed: {seed}");
+    }
+    println!("{}", input.trim_end());
+
+    Ok(())
+}
+
+fn regression(year: Option<u16>, inputs: &Inputs) -> Result<ExitCode> {
+    // failures are reported in the table, the panic messages would only drown it out
+    let outcomes = quietly(|| regression::run(aoc::DAYS.iter().filter(|d| year.is_none_or(|y| d.year == y)), inputs))?;
+
+    println!(
+        "{:<4}  {:>3}  {:>4}  {:<7}  {:<20}  {:<20}",
+        "year", "day", "part", "status", "answer", "expected"
+    );
+    for o in &outcomes {
+        let status = match o.status {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+            Status::Error => "ERROR",
+        };
+        let answer = o
+            .error
+            .as_deref()
+            .or(o.answer.as_deref())
+            .map(|s| short(s, 20))
+            .unwrap_or_default();
+        let expected = o.expected.as_deref().map(|s| short(s, 20)).unwrap_or_default();
+
+        println!(
+            "{:<4}  {:>3}  {:>4}  {:<7}  {:<20}  {:<20}",
+            o.year, o.day, o.part, status, answer, expected
+        );
+    }
+
+    let count = |s: Status| outcomes.iter().filter(|o| o.status == s).count();
+    println!();
+    println!(
+        "{} passed, {} failed, {} missing, {} errors",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing),
+        count(Status::Error)
+    );
+
+    if count(Status::Fail) + count(Status::Error) > 0 {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Keeps the table readable for multi-line art and long error messages, at most `width` characters.
+fn short(s: &str, width: usize) -> String {
+    let first = s.lines().next().unwrap_or_default();
+    if first.len() < s.len() || first.chars().count() > width {
+        return format!("{}...", first.chars().take(width - 3).collect::<String>());
+    }
+    first.to_string()
+}
+
+fn record(year: u16, day: u8, part: Option<u8>, inputs: &Inputs) -> Result<()> {
+    let d = aoc::find(year, day).ok_or_else(|| eyre!("There is no solution for {year} day {day}!"))?;
+    let source = inputs.read(year, day)?;
+    let params = d.params()?;
+    let mut answers = Answers::load(year)?;
+
+    for p in parts(part) {
+        let answer = solve(d, p, &source, &params)?;
+        if answer.is_empty() {
+            println!("{year} day {day:02} part {p} has no answer yet, skipping");
+            continue;
+        }
+
+        println!("{year} day {day:02} part {p}: {answer}");
+        answers.set(day, p, answer.to_string())?;
+    }
+
+    answers.save(year)
+}
+
+fn bench_report(baseline: Option<String>, format: Format) -> Result<()> {
+    let measurements = bench::collect(&bench::criterion_dir(), baseline.as_deref())?;
+    if measurements.is_empty() {
+        return Err(eyre!("No benchmark results yet, run `cargo bench -p aoc` first!"));
+    }
+
+    match format {
+        Format::Json => println!("{}", bench::to_json(&measurements)?),
+        Format::Csv => print!("{}", bench::to_csv(&measurements)),
+        Format::Table => {
+            println!(
+                "{:<24}  {:>12}  {:>12}  {:>8}",
+                "benchmark", "mean", "baseline", "change"
+            );
+            for m in &measurements {
+                let baseline = m.baseline_ns.map(duration).unwrap_or_default();
+                let change = m.change().map(|c| format!("{:+.1}%", c * 100.0)).unwrap_or_default();
+                println!(
+                    "{:<24}  {:>12}  {:>12}  {:>8}",
+                    m.id,
+                    duration(m.mean_ns),
+                    baseline,
+                    change
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn bytes(n: usize) -> String {
+    match n as f64 {
+        b if b >= 1024.0 * 1024.0 * 1024.0 => format!("{:.2} GiB", b / (1024.0 * 1024.0 * 1024.0)),
+        b if b >= 1024.0 * 1024.0 => format!("{:.2} MiB", b / (1024.0 * 1024.0)),
+        b if b >= 1024.0 => format!("{:.2} KiB", b / 1024.0),
+        _ => format!("{n} B"),
+    }
+}
+
+fn read_input(path: PathBuf) -> Result<String> {
+    if path.as_os_str() == "-" {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source)?;
+        return Ok(source);
+    }
+
+    fs::read_to_string(&path).map_err(|e| eyre!("Cannot read {}: {e}", path.display()))
+}
@@ -0,0 +1,281 @@
+//! Answers remembered between runs, so that re-running a slow part after unrelated edits takes no time.
+//!
+//! Every computed answer is appended to `target/aoc/memo.jsonl` along with what it depends on: a hash of the input
+//! and its parameters, and a hash of the sources of the day's crate and of every crate it uses.
+//! The same hashes give back the latest answer, and the file keeps every timing ever taken as the history of a day.
+
+use std::{
+    collections::BTreeSet,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_common::Params;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// What an answer depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub source: String,
+}
+
+impl Key {
+    /// `source` is the [`source_hash`] of the day's crate, the same for both parts.
+    pub fn new(year: u16, day: u8, part: u8, input: &str, params: &Params, source: &str) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(input);
+        hasher.update(format!("\0{params:?}"));
+
+        Self {
+            year,
+            day,
+            part,
+            input: format!("{:x}", hasher.finalize()),
+            source: source.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub source: String,
+    pub answer: String,
+    pub time_ns: u64,
+    /// Seconds since the epoch.
+    pub at: u64,
+}
+
+impl Entry {
+    pub fn new(key: Key, answer: String, time: Duration) -> Self {
+        let at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Self {
+            year: key.year,
+            day: key.day,
+            part: key.part,
+            input: key.input,
+            source: key.source,
+            answer,
+            time_ns: time.as_nanos() as u64,
+            at,
+        }
+    }
+
+    fn matches(&self, key: &Key) -> bool {
+        (self.year, self.day, self.part) == (key.year, key.day, key.part)
+            && self.input == key.input
+            && self.source == key.source
+    }
+}
+
+pub struct Memo {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Memo {
+    pub fn default_path() -> PathBuf {
+        crate::repo_root().join("target/aoc/memo.jsonl")
+    }
+
+    /// A missing file is an empty memo, and lines that no longer parse are skipped.
+    pub fn load(path: &Path) -> Result<Self> {
+        let entries = match fs::read_to_string(path) {
+            Ok(s) => s.lines().filter_map(|l| serde_json::from_str(l).ok()).collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// The latest answer computed for exactly this key.
+    pub fn get(&self, key: &Key) -> Option<&Entry> {
+        self.entries.iter().rev().find(|e| e.matches(key))
+    }
+
+    pub fn add(&mut self, entry: Entry) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        self.entries.push(entry);
+
+        Ok(())
+    }
+
+    /// Every run of a day, oldest first.
+    pub fn history(&self, year: u16, day: u8) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(move |e| (e.year, e.day) == (year, day))
+    }
+}
+
+/// A hash of the crate in `dir` along with every crate it depends on by path, which is all the code its answers come from.
+/// The tests, benches and inputs do not count.
+pub fn source_hash(dir: &Path) -> Result<String> {
+    let mut crates = BTreeSet::new();
+    collect_crates(&fs::canonicalize(dir)?, &mut crates)?;
+
+    let mut hasher = Sha256::new();
+    for dir in crates {
+        for file in files(&dir.join("src"))?.into_iter().chain([dir.join("Cargo.toml")]) {
+            hasher.update(file.strip_prefix(&dir).unwrap_or(&file).to_string_lossy().as_bytes());
+            hasher.update(fs::read(&file)?);
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn collect_crates(dir: &Path, crates: &mut BTreeSet<PathBuf>) -> Result<()> {
+    if !crates.insert(dir.to_path_buf()) {
+        return Ok(());
+    }
+
+    let manifest: toml::Table = toml::from_str(&fs::read_to_string(dir.join("Cargo.toml"))?)?;
+    let deps = manifest
+        .get("dependencies")
+        .and_then(|d| d.as_table())
+        .cloned()
+        .unwrap_or_default();
+    for path in deps.values().filter_map(|d| d.get("path")?.as_str()) {
+        collect_crates(&fs::canonicalize(dir.join(path))?, crates)?;
+    }
+
+    Ok(())
+}
+
+/// Every file under `dir`, sorted so that the hash does not depend on the order the file system lists them in.
+fn files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut res = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            res.extend(files(&path)?);
+        } else {
+            res.push(path);
+        }
+    }
+
+    res.sort();
+    Ok(res)
+}
+
+/// `YYYY-MM-DD HH:MM` in UTC, from seconds since the epoch.
+pub fn date(secs: u64) -> String {
+    let (days, rest) = ((secs / 86400) as i64, secs % 86400);
+
+    // the civil calendar from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-memo-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_memo() {
+        let dir = temp("store");
+        let path = dir.join("memo.jsonl");
+        let key = |part, input| Key::new(2022, 11, part, input, &Params::new(), "abc");
+
+        let mut memo = Memo::load(&path).unwrap();
+        assert_eq!(memo.get(&key(2, "x")), None);
+
+        memo.add(Entry::new(key(2, "x"), "10".into(), Duration::from_secs(2)))
+            .unwrap();
+        memo.add(Entry::new(key(2, "x"), "12".into(), Duration::from_secs(1)))
+            .unwrap();
+        memo.add(Entry::new(key(1, "x"), "5".into(), Duration::from_secs(1)))
+            .unwrap();
+
+        let memo = Memo::load(&path).unwrap();
+        assert_eq!(memo.get(&key(2, "x")).unwrap().answer, "12");
+        assert_eq!(memo.get(&key(2, "y")), None);
+        assert_ne!(
+            key(2, "x"),
+            Key::new(2022, 11, 2, "x", &Params::new().with("row", 10), "abc")
+        );
+        assert_eq!(memo.history(2022, 11).count(), 3);
+        assert_eq!(memo.history(2022, 10).count(), 0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_source_hash() {
+        let dir = temp("crates");
+        let write = |path: &str, contents: &str| {
+            fs::create_dir_all(dir.join(path).parent().unwrap()).unwrap();
+            fs::write(dir.join(path), contents).unwrap();
+        };
+        write(
+            "day/Cargo.toml",
+            "[dependencies]\ncommon = { path = \"../common\" }\n[dev-dependencies]\ntest = { path = \"../test\" }\n",
+        );
+        write("day/src/lib.rs", "fn part1() {}");
+        write("common/Cargo.toml", "[dependencies]\n");
+        write("common/src/lib.rs", "pub fn parse() {}");
+        write("test/Cargo.toml", "");
+
+        let before = source_hash(&dir.join("day")).unwrap();
+        assert_eq!(source_hash(&dir.join("day")).unwrap(), before);
+
+        write("day/tests/other.rs", "#[test] fn test() {}");
+        write("test/src/lib.rs", "");
+        assert_eq!(source_hash(&dir.join("day")).unwrap(), before);
+
+        write("common/src/lib.rs", "pub fn parse() { todo!() }");
+        assert_ne!(source_hash(&dir.join("day")).unwrap(), before);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(date(0), "1970-01-01 00:00");
+        assert_eq!(date(1_670_000_000), "2022-12-02 16:53");
+        assert_eq!(date(951_782_400), "2000-02-29 00:00");
+    }
+}
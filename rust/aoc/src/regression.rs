@@ -0,0 +1,68 @@
+use color_eyre::Result;
+
+use crate::{answers::Answers, inputs::Inputs, Day};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// No answer was recorded for this part yet.
+    Missing,
+    /// The solution returned an error, panicked or had no input to run on.
+    Error,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Re-runs both parts of every given day on its real input and compares them to the recorded answers.
+pub fn run<'a>(days: impl IntoIterator<Item = &'a Day>, inputs: &Inputs) -> Result<Vec<Outcome>> {
+    let mut outcomes = Vec::new();
+    let mut answers: Option<(u16, Answers)> = None;
+
+    for d in days {
+        if answers.as_ref().map(|(y, _)| *y) != Some(d.year) {
+            answers = Some((d.year, Answers::load(d.year)?));
+        }
+        let recorded = &answers.as_ref().unwrap().1;
+
+        let source = inputs.read(d.year, d.day);
+        let params = d.params()?;
+
+        for part in [1, 2] {
+            let expected = recorded.get(d.day, part).map(str::to_string);
+
+            let answer = match &source {
+                Ok(source) => d.solve(part, source, &params).map(|a| a.to_string()),
+                Err(e) => Err(color_eyre::eyre::eyre!("{e}")),
+            };
+
+            let (status, answer, error) = match (answer, &expected) {
+                (Err(e), _) => (Status::Error, None, Some(e.to_string())),
+                (Ok(a), None) => (Status::Missing, Some(a), None),
+                (Ok(a), Some(e)) if &a == e => (Status::Pass, Some(a), None),
+                (Ok(a), Some(_)) => (Status::Fail, Some(a), None),
+            };
+
+            outcomes.push(Outcome {
+                year: d.year,
+                day: d.day,
+                part,
+                status,
+                answer,
+                expected,
+                error,
+            });
+        }
+    }
+
+    Ok(outcomes)
+}
@@ -0,0 +1,83 @@
+use std::{
+    cmp::Reverse,
+    time::{Duration, Instant},
+};
+
+use color_eyre::{eyre::eyre, Result};
+use rayon::prelude::*;
+
+use crate::{alloc, inputs::Inputs, Day};
+
+/// One part of one day, run on its real input.
+#[derive(Debug)]
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub time: Duration,
+    /// The most bytes the part had allocated at once.
+    pub peak: usize,
+}
+
+/// Runs both parts of every given day on a pool of `jobs` threads (one per core by default), the costliest first.
+pub fn run<'a>(days: impl IntoIterator<Item = &'a Day>, jobs: Option<usize>, inputs: &Inputs) -> Result<Vec<Run>> {
+    let mut tasks = vec![];
+    for d in days {
+        // a missing input is reported with the parts, like a failing solution
+        let source = inputs.read(d.year, d.day).map_err(|e| e.to_string());
+        let params = d.params()?;
+        for part in [1, 2] {
+            tasks.push((d, part, source.clone(), params.clone()));
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs.unwrap_or(0)).build()?;
+    let mut runs: Vec<Run> = pool.install(|| {
+        tasks
+            .into_par_iter()
+            .map(|(d, part, source, params)| {
+                let start = Instant::now();
+                let (answer, peak) = alloc::measure(|| {
+                    let source = source.map_err(|e| eyre!("{e}"))?;
+                    d.solve(part, &source, &params)
+                });
+                let time = start.elapsed();
+
+                let (answer, error) = match answer {
+                    Ok(a) => (Some(a.to_string()), None),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+
+                Run {
+                    year: d.year,
+                    day: d.day,
+                    part,
+                    answer,
+                    error,
+                    time,
+                    peak,
+                }
+            })
+            .collect()
+    });
+
+    runs.sort_by_key(|r| Reverse(r.time));
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let days = [*crate::find(2022, 1).unwrap(), *crate::find(2022, 6).unwrap()];
+        let runs = run(&days, Some(2), &Inputs::from_env(None)).unwrap();
+
+        assert_eq!(runs.len(), 4);
+        assert!(runs.windows(2).all(|w| w[0].time >= w[1].time));
+        assert!(runs.iter().all(|r| r.answer.is_some() && r.peak > 0));
+    }
+}
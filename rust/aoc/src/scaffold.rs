@@ -0,0 +1,236 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::eyre, Result};
+
+use crate::repo_root;
+
+const MANIFEST: &str = include_str!("../templates/Cargo.toml.tmpl");
+const EXAMPLES: &str = include_str!("../templates/examples.toml.tmpl");
+const LIB: &str = include_str!("../templates/lib.rs.tmpl");
+const LIB_PARSER: &str = include_str!("../templates/lib_parser.rs.tmpl");
+const PARSER: &str = include_str!("../templates/parser.rs.tmpl");
+const PART: &str = include_str!("../templates/part.rs.tmpl");
+
+/// A new day crate, generated from the templates in `rust/aoc/templates`.
+pub struct Scaffold {
+    pub year: u16,
+    pub day: u8,
+    /// Start with a nom `parser.rs` module.
+    pub parser: bool,
+}
+
+impl Scaffold {
+    pub fn new(year: u16, day: u8, parser: bool) -> Result<Self> {
+        if !(2015..=2100).contains(&year) || !(1..=25).contains(&day) {
+            return Err(eyre!("There is no Advent of Code puzzle for {year} day {day}!"));
+        }
+
+        Ok(Self { year, day, parser })
+    }
+
+    /// Directory of the crate, relative to the repository root.
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(format!("{}/rust/day-{:02}", self.year, self.day))
+    }
+
+    fn fill(&self, template: &str) -> String {
+        template
+            .replace("{year}", &self.year.to_string())
+            .replace("{day}", &format!("{:02}", self.day))
+    }
+
+    /// Every file of the crate, relative to its directory.
+    pub fn files(&self) -> Vec<(&'static str, String)> {
+        let extra_deps = if self.parser { "nom = \"7\"\n" } else { "" };
+        let mut files = vec![
+            ("Cargo.toml", self.fill(MANIFEST).replace("{extra_deps}", extra_deps)),
+            ("src/bin/part1.rs", self.fill(PART).replace("{part}", "1")),
+            ("src/bin/part2.rs", self.fill(PART).replace("{part}", "2")),
+            ("test_input.txt", String::new()),
+            ("examples.toml", EXAMPLES.to_string()),
+            ("real_input.txt", String::new()),
+        ];
+
+        if self.parser {
+            files.push(("src/lib.rs", self.fill(LIB_PARSER)));
+            files.push(("src/parser.rs", self.fill(PARSER)));
+        } else {
+            files.push(("src/lib.rs", self.fill(LIB)));
+        }
+
+        files
+    }
+
+    /// Writes the crate under `root` and registers it with the workspace and the runner.
+    pub fn create(&self, root: &Path) -> Result<PathBuf> {
+        let dir = root.join(self.dir());
+        if dir.exists() {
+            return Err(eyre!("{} already exists!", dir.display()));
+        }
+
+        for (path, content) in self.files() {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, content)?;
+        }
+
+        update(&root.join("Cargo.toml"), |s| add_member(s, self.year))?;
+        update(&root.join("rust/aoc/Cargo.toml"), |s| {
+            add_dependency(s, self.year, self.day)
+        })?;
+        update(&root.join("rust/aoc/src/days.rs"), |s| add_day(s, self.year, self.day))?;
+
+        Ok(dir)
+    }
+}
+
+/// Creates the crate in this repository.
+pub fn create(year: u16, day: u8, parser: bool) -> Result<PathBuf> {
+    Scaffold::new(year, day, parser)?.create(&repo_root())
+}
+
+fn update(path: &Path, f: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    let source = fs::read_to_string(path).map_err(|e| eyre!("Cannot read {}: {e}", path.display()))?;
+    fs::write(path, f(&source)?)?;
+    Ok(())
+}
+
+/// Inserts `line` among the lines with a `key`, so that they stay sorted.
+/// `separate` tells whether two keys belong to different groups, which are kept apart by a blank line.
+fn insert_sorted<K: Ord + Copy>(
+    source: &str,
+    line: &str,
+    new: K,
+    key: impl Fn(&str) -> Option<K>,
+    separate: impl Fn(K, K) -> bool,
+) -> Result<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let keyed: Vec<(usize, K)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, key(l)?)))
+        .collect();
+
+    if keyed.iter().any(|&(_, k)| k == new) {
+        return Err(eyre!("{line:?} is already there!"));
+    }
+
+    let prev = keyed.iter().rev().find(|&&(_, k)| k < new).copied();
+    let next = keyed.iter().find(|&&(_, k)| k > new).copied();
+
+    let mut res: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    let line = line.to_string();
+    match (prev, next) {
+        (Some((i, k)), _) if !separate(k, new) => res.insert(i + 1, line),
+        (_, Some((i, k))) if !separate(new, k) => res.insert(i, line),
+        // a group of its own, e.g. the first day of a year
+        (Some((i, _)), _) => {
+            res.insert(i + 1, line);
+            res.insert(i + 1, String::new());
+        }
+        (None, Some((i, _))) => {
+            res.insert(i, String::new());
+            res.insert(i, line);
+        }
+        (None, None) => return Err(eyre!("Found nowhere to put {line:?}!")),
+    }
+
+    Ok(res.join("\n") + "\n")
+}
+
+/// Parses the `2022` and `7` out of a `2022...07...` line.
+fn year_day(line: &str, prefix: &str) -> Option<(u16, u8)> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let year = rest.get(..4)?.parse().ok()?;
+    let day = rest.get(4..)?.trim_start_matches(|c: char| !c.is_ascii_digit());
+    let day = day.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+    Some((year, day))
+}
+
+/// Adds the year's days to the workspace members, unless they already are.
+pub fn add_member(workspace: &str, year: u16) -> Result<String> {
+    let key = |l: &str| {
+        let l = l.trim().strip_prefix('"')?;
+        l.ends_with("/rust/day-*\",").then(|| l.get(..4)?.parse::<u16>().ok())?
+    };
+    if workspace.lines().any(|l| key(l) == Some(year)) {
+        return Ok(workspace.to_string());
+    }
+
+    insert_sorted(workspace, &format!("    \"{year}/rust/day-*\","), year, key, |_, _| {
+        false
+    })
+}
+
+/// Adds the day crate to the dependencies of the runner, every year in its own block.
+pub fn add_dependency(manifest: &str, year: u16, day: u8) -> Result<String> {
+    let line = format!("aoc-{year}-day-{day:02} = {{ path = \"../../{year}/rust/day-{day:02}\" }}");
+    insert_sorted(manifest, &line, (year, day), |l| year_day(l, "aoc-"), |a, b| a.0 != b.0)
+}
+
+/// Adds the day to [`DAYS`](crate::DAYS), in calendar order.
+pub fn add_day(days: &str, year: u16, day: u8) -> Result<String> {
+    let line = format!("    day!({year}, {day}, aoc_{year}_day_{day:02}),");
+    insert_sorted(days, &line, (year, day), |l| year_day(l, "day!("), |_, _| false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files() {
+        let s = Scaffold::new(2023, 3, true).unwrap();
+        let files = s.files();
+        let get = |name| &files.iter().find(|(p, _)| *p == name).unwrap().1;
+
+        assert!(get("Cargo.toml").contains("name = \"aoc-2023-day-03-part2\""));
+        assert!(get("Cargo.toml").contains("nom = \"7\""));
+        assert!(get("src/bin/part1.rs").contains("use aoc_2023_day_03::part1;"));
+        assert!(get("src/lib.rs").starts_with("mod parser;"));
+        assert!(get("src/lib.rs").contains("aoc_common::examples!(Solver);"));
+        assert!(get("examples.toml").contains("input = \"test_input.txt\""));
+        assert_eq!(s.dir(), PathBuf::from("2023/rust/day-03"));
+
+        let s = Scaffold::new(2023, 3, false).unwrap();
+        assert!(s
+            .files()
+            .iter()
+            .all(|(p, c)| *p != "src/parser.rs" && !c.contains("{day}")));
+        assert!(Scaffold::new(2023, 26, false).is_err());
+    }
+
+    #[test]
+    fn test_register() {
+        let workspace = "members = [\n    \"2019/rust/day-*\",\n    \"2022/rust/day-*\",\n    \"rust/*\",\n]\n";
+        assert_eq!(
+            add_member(workspace, 2020).unwrap(),
+            "members = [\n    \"2019/rust/day-*\",\n    \"2020/rust/day-*\",\n    \"2022/rust/day-*\",\n    \"rust/*\",\n]\n"
+        );
+        assert_eq!(add_member(workspace, 2022).unwrap(), workspace);
+
+        let manifest = "[dependencies]\nclap = \"4\"\n\naoc-2019-day-01 = { path = \"../../2019/rust/day-01\" }\n\naoc-2022-day-01 = { path = \"../../2022/rust/day-01\" }\naoc-2022-day-05 = { path = \"../../2022/rust/day-05\" }\n";
+        let res = add_dependency(manifest, 2022, 3).unwrap();
+        assert!(res.contains("day-01\" }\naoc-2022-day-03 = { path = \"../../2022/rust/day-03\" }\naoc-2022-day-05"));
+        let res = add_dependency(manifest, 2022, 1);
+        assert!(res.is_err());
+        let res = add_dependency(&manifest.replace("aoc-2022-day-01", "aoc-2022-day-02"), 2022, 1).unwrap();
+        assert!(res.contains(
+            "2019/rust/day-01\" }\n\naoc-2022-day-01 = { path = \"../../2022/rust/day-01\" }\naoc-2022-day-02"
+        ));
+        let res = add_dependency(manifest, 2020, 1).unwrap();
+        assert!(
+            res.contains("2019/rust/day-01\" }\n\naoc-2020-day-01 = { path = \"../../2020/rust/day-01\" }\n\naoc-2022")
+        );
+        assert!(add_dependency(manifest, 2022, 5).is_err());
+
+        let days = "pub static DAYS: &[Day] = &[\n    day!(2019, 1, aoc_2019_day_01),\n    day!(2022, 10, aoc_2022_day_10),\n];\n";
+        assert_eq!(
+            add_day(days, 2022, 9).unwrap(),
+            "pub static DAYS: &[Day] = &[\n    day!(2019, 1, aoc_2019_day_01),\n    day!(2022, 9, aoc_2022_day_09),\n    day!(2022, 10, aoc_2022_day_10),\n];\n"
+        );
+    }
+}
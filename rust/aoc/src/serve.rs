@@ -0,0 +1,340 @@
+//! A local web page that runs any day on a pasted input and plays its frames, for demos and for debugging away
+//! from the terminal. Nothing leaves the machine, the page is served from here and has no outside resources.
+
+use std::{
+    fmt::Write,
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use aoc_common::{frames::Frames, Answer, Frame, Params};
+use color_eyre::{eyre::eyre, Result};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{duration, inputs::Inputs, panic_message, status::escape, Day, DAYS};
+
+/// The browser gets at most this many frames of a part, the big simulations are thinned out evenly.
+const MAX_FRAMES: usize = 300;
+
+/// The largest form read, well above any puzzle input.
+const MAX_BODY: u64 = 16 << 20;
+
+pub fn serve(addr: &str, inputs: &Inputs) -> Result<()> {
+    let server = Server::http(addr).map_err(|e| eyre!("Cannot listen on {addr}: {e}"))?;
+    println!("Serving on http://{}", server.server_addr());
+
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        // one byte more than allowed, to tell a full body from a cut one
+        let page = match request.as_reader().take(MAX_BODY + 1).read_to_string(&mut body) {
+            Ok(n) if n as u64 > MAX_BODY => Page::new(413, format!("The request is larger than {MAX_BODY} bytes")),
+            Ok(_) => respond(request.method(), request.url(), &body, inputs),
+            Err(e) => Page::new(400, format!("Cannot read the request: {e}")),
+        };
+
+        let header = Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap();
+        let response = Response::from_string(page.body)
+            .with_status_code(page.status)
+            .with_header(header);
+        if let Err(e) = request.respond(response) {
+            eprintln!("Cannot respond: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct Page {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Page {
+    fn new(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+}
+
+/// Answers one request, `GET /` for the form and `POST /run` for the form with the results below it.
+pub fn respond(method: &Method, url: &str, body: &str, inputs: &Inputs) -> Page {
+    match (method, url) {
+        (Method::Get, "/") => Page::new(200, page(&Form::default(), &[])),
+        (Method::Post, "/run") => {
+            let form = Form::parse(body);
+            match run(&form, inputs) {
+                Ok(outcomes) => Page::new(200, page(&form, &outcomes)),
+                Err(e) => Page::new(400, page(&form, &[Outcome::failed(&e.to_string())])),
+            }
+        }
+        _ => Page::new(404, format!("There is nothing at {}", escape(url))),
+    }
+}
+
+/// What was filled in, kept to show the form again with the results.
+#[derive(Debug, Default, PartialEq)]
+struct Form {
+    /// `year-day`, like `2022-14`.
+    day: String,
+    /// Empty for both.
+    part: String,
+    /// Empty for the day's real input.
+    input: String,
+    /// `name=value` lines.
+    params: String,
+}
+
+impl Form {
+    fn parse(body: &str) -> Self {
+        let mut form = Form::default();
+        for (key, value) in form_urlencoded::parse(body.as_bytes()) {
+            let value = value.replace("\r\n", "\n");
+            match key.as_ref() {
+                "day" => form.day = value,
+                "part" => form.part = value,
+                "input" => form.input = value,
+                "params" => form.params = value,
+                _ => {}
+            }
+        }
+        form
+    }
+}
+
+/// One part run for the page.
+#[derive(Debug)]
+struct Outcome {
+    title: String,
+    answer: Result<Answer, String>,
+    time: Duration,
+    frames: Vec<Frame>,
+}
+
+impl Outcome {
+    fn failed(error: &str) -> Self {
+        Self {
+            title: "Error".to_string(),
+            answer: Err(error.to_string()),
+            time: Duration::ZERO,
+            frames: vec![],
+        }
+    }
+}
+
+fn run(form: &Form, inputs: &Inputs) -> Result<Vec<Outcome>> {
+    let d = form
+        .day
+        .split_once('-')
+        .and_then(|(year, day)| crate::find(year.parse().ok()?, day.parse().ok()?))
+        .ok_or_else(|| eyre!("There is no solution for {:?}!", form.day))?;
+
+    let source = if form.input.trim().is_empty() {
+        inputs.read(d.year, d.day)?
+    } else {
+        form.input.clone()
+    };
+    // pasted input is most likely a real one too, so both use the real params
+    let mut params = d.params()?;
+    for line in form.params.lines().filter(|l| !l.trim().is_empty()) {
+        params.merge(line.parse()?);
+    }
+
+    let parts = match form.part.as_str() {
+        "" => vec![1, 2],
+        p => vec![p.parse()?],
+    };
+
+    Ok(parts.into_iter().map(|part| solve(d, part, &source, &params)).collect())
+}
+
+/// Runs the part's visualisation, which gives both the frames and the answer, or the part on its own if it has none.
+/// The time includes drawing the frames.
+fn solve(d: &Day, part: u8, source: &str, params: &Params) -> Outcome {
+    let mut frames = Frames::default();
+    let start = Instant::now();
+    let watched = panic::catch_unwind(AssertUnwindSafe(|| {
+        d.solution.visualize_with(part, source, params, &mut frames)
+    }));
+    let answer = match watched {
+        Ok(Some(answer)) => answer,
+        Ok(None) => d.solve(part, source, params),
+        Err(payload) => Err(eyre!(
+            "{} day {} part {part} panicked: {}",
+            d.year,
+            d.day,
+            panic_message(&payload)
+        )),
+    };
+    let time = start.elapsed();
+
+    let frames = match answer {
+        Ok(_) => thin(frames.0, MAX_FRAMES),
+        Err(_) => vec![],
+    };
+    let answer = answer.map_err(|e| e.to_string());
+
+    Outcome {
+        title: format!("{} day {:02} part {part}", d.year, d.day),
+        answer,
+        time,
+        frames,
+    }
+}
+
+/// At most `max` of the frames, evenly spread and always with the last one.
+fn thin(frames: Vec<Frame>, max: usize) -> Vec<Frame> {
+    if frames.len() <= max {
+        return frames;
+    }
+
+    let last = frames.len() - 1;
+    let keep: Vec<usize> = (0..max).map(|n| n * last / (max - 1)).collect();
+    frames
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| keep.binary_search(i).is_ok())
+        .map(|(_, f)| f)
+        .collect()
+}
+
+const HEAD: &str =
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code</title>\n<style>\n\
+    body { font-family: monospace; background: #0f0f23; color: #ccc; }\n\
+    textarea { width: 60em; height: 12em; background: #10101a; color: #ccc; }\n\
+    .answer { color: #ffff66; } .error { color: #f66; } .time { color: #888; }\n\
+    pre.frame { line-height: 1; font-size: 10px; }\n\
+    </style>\n<script>\n\
+    function player(id, frames) {\n\
+      const pre = document.getElementById('frame-' + id), slider = document.getElementById('slider-' + id);\n\
+      let timer = null;\n\
+      const show = i => { slider.value = i; pre.textContent = frames[i]; };\n\
+      slider.oninput = () => show(+slider.value);\n\
+      document.getElementById('play-' + id).onclick = () => {\n\
+        if (timer) { clearInterval(timer); timer = null; return; }\n\
+        timer = setInterval(() => show((+slider.value + 1) % frames.length), 100);\n\
+      };\n\
+      show(frames.length - 1);\n\
+    }\n\
+    </script>\n</head>\n<body>\n<h1>Advent of Code</h1>\n";
+
+fn page(form: &Form, outcomes: &[Outcome]) -> String {
+    let mut res = String::from(HEAD);
+
+    res.push_str("<form method=\"post\" action=\"/run\">\n<p><select name=\"day\">\n");
+    for d in DAYS {
+        let value = format!("{}-{}", d.year, d.day);
+        let selected = if value == form.day { " selected" } else { "" };
+        writeln!(
+            res,
+            "<option value=\"{value}\"{selected}>{} day {:02}</option>",
+            d.year, d.day
+        )
+        .unwrap();
+    }
+    res.push_str("</select>\n<select name=\"part\">\n");
+    for (value, name) in [("", "both parts"), ("1", "part 1"), ("2", "part 2")] {
+        let selected = if value == form.part { " selected" } else { "" };
+        writeln!(res, "<option value=\"{value}\"{selected}>{name}</option>").unwrap();
+    }
+    writeln!(
+        res,
+        "</select>\n<button>Run</button></p>\n\
+         <p><textarea name=\"input\" placeholder=\"The puzzle input, the day's real input if empty\">{}</textarea></p>\n\
+         <p><textarea name=\"params\" placeholder=\"name=value, one per line\" style=\"height: 3em\">{}</textarea></p>\n\
+         </form>",
+        escape(&form.input),
+        escape(&form.params)
+    )
+    .unwrap();
+
+    for (id, o) in outcomes.iter().enumerate() {
+        writeln!(res, "<h2>{}</h2>", o.title).unwrap();
+        match &o.answer {
+            Ok(answer) => writeln!(res, "<pre class=\"answer\">{}</pre>", escape(&answer.to_string())).unwrap(),
+            Err(e) => writeln!(res, "<pre class=\"error\">{}</pre>", escape(e)).unwrap(),
+        }
+        if !o.time.is_zero() {
+            writeln!(res, "<p class=\"time\">{}</p>", duration(o.time.as_nanos() as f64)).unwrap();
+        }
+
+        if !o.frames.is_empty() {
+            let frames: Vec<String> = o.frames.iter().map(Frame::to_string).collect();
+            // a frame could contain `</script>`
+            let json = serde_json::to_string(&frames).unwrap().replace("</", "<\\/");
+            writeln!(
+                res,
+                "<p><button id=\"play-{id}\">play / pause</button> \
+                 <input type=\"range\" id=\"slider-{id}\" min=\"0\" max=\"{}\"></p>\n\
+                 <pre class=\"frame\" id=\"frame-{id}\"></pre>\n<script>player({id}, {json});</script>",
+                frames.len() - 1
+            )
+            .unwrap();
+        }
+    }
+
+    res.push_str("</body>\n</html>\n");
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form() {
+        let form = Form::parse("day=2022-10&part=2&input=noop%0D%0Aaddx+3&params=row%3D10&other=x");
+        assert_eq!(
+            form,
+            Form {
+                day: "2022-10".to_string(),
+                part: "2".to_string(),
+                input: "noop\naddx 3".to_string(),
+                params: "row=10".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_thin() {
+        let frames: Vec<Frame> = (0..1000).map(|n| Frame::new(&n.to_string())).collect();
+        let thinned = thin(frames, 10);
+        assert_eq!(thinned.len(), 10);
+        assert_eq!(
+            (thinned[0].lines[0].as_str(), thinned[9].lines[0].as_str()),
+            ("0", "999")
+        );
+    }
+
+    #[test]
+    fn test_respond() {
+        let inputs = Inputs::from_env(None);
+        let index = respond(&Method::Get, "/", "", &inputs);
+        assert_eq!(index.status, 200);
+        assert_eq!(index.body.matches("<option value=\"20").count(), DAYS.len());
+
+        assert_eq!(respond(&Method::Get, "/nope", "", &inputs).status, 404);
+
+        let page = respond(&Method::Post, "/run", "day=2022-99", &inputs);
+        assert_eq!(page.status, 400);
+        assert!(page.body.contains("There is no solution for &quot;2022-99&quot;!"));
+    }
+
+    #[test]
+    fn test_run() {
+        let input = include_str!("../../../2022/rust/day-14/test_input.txt");
+        let body = format!(
+            "day=2022-14&part=1&input={}",
+            form_urlencoded::byte_serialize(input.as_bytes()).collect::<String>()
+        );
+        let page = respond(&Method::Post, "/run", &body, &Inputs::from_env(None));
+
+        assert_eq!(page.status, 200);
+        assert!(page
+            .body
+            .contains("<h2>2022 day 14 part 1</h2>\n<pre class=\"answer\">24</pre>"));
+        assert!(page.body.contains("<option value=\"2022-14\" selected>"));
+        assert!(page.body.contains("player(0, [\""));
+        assert!(page.body.contains("24 grains of sand, done"));
+    }
+}
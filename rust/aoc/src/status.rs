@@ -0,0 +1,432 @@
+//! How far along every year is: which parts are solved, stubbed, borrowed or broken, and which days are missing.
+
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc_common::examples::{self, Example};
+use color_eyre::Result;
+
+use crate::{answers::Answers, duration, inputs::Inputs, repo_root, run_all, Day, DAYS};
+
+/// The state of a part, from the best to the worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum State {
+    /// The answer is the recorded one.
+    Solved,
+    /// There is an answer, but none recorded to check it against.
+    Unverified,
+    /// Solved by code taken from someone else.
+    Borrowed,
+    /// The answer, or that of an example, is wrong.
+    Wrong,
+    /// The part returns an empty answer.
+    Stub,
+    Error,
+    /// There is no crate for the day.
+    Missing,
+}
+
+impl State {
+    pub const ALL: [State; 7] = [
+        State::Solved,
+        State::Unverified,
+        State::Borrowed,
+        State::Wrong,
+        State::Stub,
+        State::Error,
+        State::Missing,
+    ];
+
+    pub fn symbol(&self) -> char {
+        match self {
+            State::Solved => '*',
+            State::Unverified => '+',
+            State::Borrowed => 'b',
+            State::Wrong => 'x',
+            State::Stub => '-',
+            State::Error => '!',
+            State::Missing => '.',
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            State::Solved => "solved",
+            State::Unverified => "unverified",
+            State::Borrowed => "borrowed",
+            State::Wrong => "wrong",
+            State::Stub => "stub",
+            State::Error => "error",
+            State::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Part {
+    pub part: u8,
+    pub state: State,
+    /// How long the real input took.
+    pub time: Option<Duration>,
+    /// Why the part is not simply solved.
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct DayStatus {
+    pub year: u16,
+    pub day: u8,
+    pub parts: Vec<Part>,
+    /// `#[ignore]`d tests in the crate, ignored example parts included.
+    pub ignored: usize,
+}
+
+impl DayStatus {
+    fn missing(year: u16, day: u8) -> Self {
+        let parts = [1, 2]
+            .map(|part| Part {
+                part,
+                state: State::Missing,
+                time: None,
+                notes: vec![],
+            })
+            .into();
+
+        Self {
+            year,
+            day,
+            parts,
+            ignored: 0,
+        }
+    }
+
+    /// The worst state of its parts.
+    pub fn state(&self) -> State {
+        self.parts.iter().map(|p| p.state).max().unwrap_or(State::Missing)
+    }
+
+    pub fn time(&self) -> Option<Duration> {
+        self.parts.iter().map(|p| p.time).sum()
+    }
+}
+
+/// Runs every day of the given years on its real input and examples, and scans its crate, all 25 days of each year.
+pub fn collect(years: &[u16], inputs: &Inputs) -> Result<Vec<DayStatus>> {
+    let days: Vec<&Day> = DAYS.iter().filter(|d| years.contains(&d.year)).collect();
+    let runs = run_all::run(days.iter().copied(), None, inputs)?;
+
+    let mut statuses = vec![];
+    for &year in years {
+        let answers = Answers::load(year)?;
+
+        for day in 1..=25 {
+            match days.iter().find(|d| d.year == year && d.day == day) {
+                Some(d) => statuses.push(day_status(d, &answers, &runs)?),
+                None => statuses.push(DayStatus::missing(year, day)),
+            }
+        }
+    }
+
+    Ok(statuses)
+}
+
+fn day_status(d: &Day, answers: &Answers, runs: &[run_all::Run]) -> Result<DayStatus> {
+    let dir = repo_root().join(d.dir());
+    let sources = sources(&dir.join("src"))?;
+    let examples = examples::load(&dir)?;
+    let borrowed = borrowed(&sources);
+
+    let mut parts = vec![];
+    for part in [1, 2] {
+        let run = runs.iter().find(|r| (r.year, r.day, r.part) == (d.year, d.day, part));
+        let mut notes = vec![];
+
+        let mut state = match run.map(|r| (&r.answer, &r.error)) {
+            Some((_, Some(e))) => {
+                notes.push(e.clone());
+                State::Error
+            }
+            Some((Some(a), None)) if a.is_empty() => State::Stub,
+            Some((Some(a), None)) => match answers.get(d.day, part) {
+                None => State::Unverified,
+                Some(r) if r == a => State::Solved,
+                Some(r) => {
+                    notes.push(format!("gives {a:?}, {r:?} was accepted"));
+                    State::Wrong
+                }
+            },
+            _ => State::Error,
+        };
+
+        for e in examples.iter().filter(|e| e.answer(part).is_some()) {
+            if e.ignore.contains(&part) {
+                notes.push(format!("the example `{}` is ignored", e.name));
+            } else if let Some(note) = check_example(d, &dir, e, part) {
+                notes.push(note);
+                state = state.max(State::Wrong);
+            }
+        }
+
+        if let Some((_, module)) = borrowed.iter().find(|(p, _)| *p == part) {
+            notes.push(format!("delegates to the borrowed `{module}`"));
+            state = state.max(State::Borrowed);
+        }
+
+        parts.push(Part {
+            part,
+            state,
+            time: run.map(|r| r.time),
+            notes,
+        });
+    }
+
+    let ignored = sources
+        .iter()
+        .map(|(_, s)| s.matches("#[ignore").count())
+        .sum::<usize>()
+        + examples.iter().map(|e| e.ignore.len()).sum::<usize>();
+
+    Ok(DayStatus {
+        year: d.year,
+        day: d.day,
+        parts,
+        ignored,
+    })
+}
+
+/// What is wrong with the answer to the example, `None` if it is right.
+fn check_example(d: &Day, dir: &Path, example: &Example, part: u8) -> Option<String> {
+    let expected = example.answer(part)?;
+    let answer = example
+        .source(dir)
+        .and_then(|source| d.solve(part, &source, &example.params(dir)?))
+        .map(|a| a.to_string());
+
+    match answer {
+        Ok(a) if a.trim_end() == expected.trim_end() => None,
+        Ok(a) => Some(format!(
+            "the example `{}` gives {a:?}, expected {expected:?}",
+            example.name
+        )),
+        Err(e) => Some(format!("the example `{}` fails: {e}", example.name)),
+    }
+}
+
+/// Every `.rs` file under `dir` with its contents.
+fn sources(dir: &Path) -> Result<Vec<(PathBuf, String)>> {
+    let mut res = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            res.extend(sources(&path)?);
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            let source = fs::read_to_string(&path)?;
+            res.push((path, source));
+        }
+    }
+
+    Ok(res)
+}
+
+/// The parts whose `partN` function calls into a module that says it is borrowed, with that module.
+fn borrowed(sources: &[(PathBuf, String)]) -> Vec<(u8, String)> {
+    let Some((_, lib)) = sources.iter().find(|(p, _)| p.ends_with("lib.rs")) else {
+        return vec![];
+    };
+
+    let modules: Vec<String> = sources
+        .iter()
+        .filter(|(_, s)| s.to_lowercase().contains("borrowed from"))
+        .filter_map(|(p, _)| p.file_stem()?.to_str().map(str::to_string))
+        .filter(|m| m != "lib")
+        .collect();
+
+    let mut res = vec![];
+    for part in [1, 2] {
+        let Some(start) = lib.find(&format!("fn part{part}(")) else {
+            continue;
+        };
+        // up to the closing brace at the start of a line
+        let body = &lib[start..];
+        let body = &body[..body.find("\n}").unwrap_or(body.len())];
+
+        if let Some(m) = modules.iter().find(|m| body.contains(&format!("{m}::"))) {
+            res.push((part, m.clone()));
+        }
+    }
+
+    res
+}
+
+fn parts(d: &DayStatus) -> String {
+    d.parts.iter().map(|p| p.state.symbol()).collect()
+}
+
+fn time(d: &DayStatus) -> String {
+    d.time().map(|t| duration(t.as_nanos() as f64)).unwrap_or_default()
+}
+
+fn legend() -> String {
+    State::ALL
+        .iter()
+        .map(|s| format!("{} {}", s.symbol(), s.name()))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// A calendar of each year for the terminal, five days to a row, followed by whatever needs attention.
+pub fn calendar(statuses: &[DayStatus]) -> String {
+    let mut res = String::new();
+
+    for (i, d) in statuses.iter().enumerate() {
+        if i == 0 || statuses[i - 1].year != d.year {
+            if i > 0 {
+                res.push('\n');
+            }
+            writeln!(res, "{}", d.year).unwrap();
+        }
+
+        let cell = format!("{:>2} {} {:>9}", d.day, parts(d), time(d));
+        if d.day % 5 == 0 {
+            writeln!(res, "{}", cell.trim_end()).unwrap();
+        } else {
+            write!(res, "{cell}   ").unwrap();
+        }
+    }
+
+    writeln!(res, "\n{}", legend()).unwrap();
+
+    let attention: Vec<String> = statuses
+        .iter()
+        .filter(|d| d.state() != State::Missing)
+        .flat_map(|d| {
+            let ignored = (d.ignored > 0).then(|| format!("{} day {:02}: {} ignored tests", d.year, d.day, d.ignored));
+            d.parts
+                .iter()
+                .filter(|p| p.state != State::Solved)
+                .map(move |p| {
+                    let mut line = format!("{} day {:02} part {}: {}", d.year, d.day, p.part, p.state.name());
+                    if !p.notes.is_empty() {
+                        write!(line, ", {}", p.notes.join(", ")).unwrap();
+                    }
+                    line
+                })
+                .chain(ignored)
+        })
+        .collect();
+
+    if !attention.is_empty() {
+        writeln!(res, "\n{}", attention.join("\n")).unwrap();
+    }
+
+    res
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The same calendar as a standalone HTML page, the details of each day in its tooltip.
+pub fn html(statuses: &[DayStatus]) -> String {
+    let mut res = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code</title>\n<style>\n\
+         body { font-family: monospace; background: #0f0f23; color: #ccc; }\n\
+         .year { display: grid; grid-template-columns: repeat(5, 9em); gap: 0.4em; margin-bottom: 2em; }\n\
+         .day { padding: 0.4em; border: 1px solid #333; }\n\
+         .time { float: right; color: #888; }\n\
+         .solved { color: #ffff66; } .unverified { color: #9f9; } .borrowed { color: #99f; }\n\
+         .wrong, .error { color: #f66; } .stub { color: #fa6; } .missing { color: #444; }\n\
+         </style>\n</head>\n<body>\n<h1>Advent of Code</h1>\n",
+    );
+
+    for (i, d) in statuses.iter().enumerate() {
+        if i == 0 || statuses[i - 1].year != d.year {
+            if i > 0 {
+                res.push_str("</div>\n");
+            }
+            writeln!(res, "<h2>{}</h2>\n<div class=\"year\">", d.year).unwrap();
+        }
+
+        let mut title: Vec<String> = d
+            .parts
+            .iter()
+            .map(|p| {
+                let time = p
+                    .time
+                    .map(|t| format!(" in {}", duration(t.as_nanos() as f64)))
+                    .unwrap_or_default();
+                let notes = p.notes.iter().map(|n| format!(", {n}")).collect::<String>();
+                format!("part {}: {}{time}{notes}", p.part, p.state.name())
+            })
+            .collect();
+        if d.ignored > 0 {
+            title.push(format!("{} ignored tests", d.ignored));
+        }
+
+        writeln!(
+            res,
+            "<div class=\"day {}\" title=\"{}\">{:>2} {}<span class=\"time\">{}</span></div>",
+            d.state().name(),
+            escape(&title.join("\n")),
+            d.day,
+            escape(&parts(d)),
+            time(d)
+        )
+        .unwrap();
+    }
+
+    if !statuses.is_empty() {
+        res.push_str("</div>\n");
+    }
+    writeln!(res, "<p>{}</p>\n</body>\n</html>", escape(&legend())).unwrap();
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed() {
+        let sources = vec![
+            (
+                PathBuf::from("src/lib.rs"),
+                "mod other;\n\npub fn part1() {\n    mine()\n}\n\npub fn part2() {\n    other::solve()\n}\n"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("src/other.rs"),
+                "//! This solution is borrowed from someone\n".to_string(),
+            ),
+        ];
+
+        assert_eq!(borrowed(&sources), vec![(2, "other".to_string())]);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut day = DayStatus::missing(2022, 1);
+        day.parts[0].state = State::Solved;
+        day.parts[1].state = State::Stub;
+        let statuses: Vec<DayStatus> = std::iter::once(day)
+            .chain((2..=25).map(|d| DayStatus::missing(2022, d)))
+            .collect();
+
+        let calendar = calendar(&statuses);
+        assert!(calendar.starts_with("2022\n 1 *- "));
+        assert!(calendar.contains("2022 day 01 part 2: stub"));
+        assert!(!calendar.contains("day 02"));
+
+        let html = html(&statuses);
+        assert!(html.contains("<div class=\"day stub\" title=\"part 1: solved\npart 2: stub\"> 1 *-"));
+        assert_eq!(html.matches("class=\"day missing\"").count(), 24);
+    }
+}
@@ -0,0 +1,281 @@
+//! The recorders behind `run --visualize`: playing the frames in the terminal or writing them to a file.
+
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use aoc_common::{Frame, Recorder};
+use color_eyre::Result;
+use serde_json::json;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Backend {
+    /// Play the frames right here
+    Terminal,
+    /// Write an asciicast v2 recording, for `asciinema play`
+    Asciicast,
+    /// Write an animated GIF
+    Gif,
+    /// Write a directory of numbered PNG images
+    Png,
+}
+
+impl Backend {
+    /// Where the recording of one part goes inside `dir`, `None` when nothing is written.
+    pub fn path(&self, dir: &Path, year: u16, day: u8, part: u8) -> Option<PathBuf> {
+        let name = format!("{year}-day-{day:02}-part{part}");
+        match self {
+            Backend::Terminal => None,
+            Backend::Asciicast => Some(dir.join(name + ".cast")),
+            Backend::Gif => Some(dir.join(name + ".gif")),
+            Backend::Png => Some(dir.join(name)),
+        }
+    }
+
+    pub fn recorder(&self, path: Option<PathBuf>, delay: Duration) -> Box<dyn Recorder> {
+        let path = path.unwrap_or_default();
+        match self {
+            Backend::Terminal => Box::new(Terminal { delay }),
+            Backend::Asciicast => Box::new(Asciicast {
+                path,
+                delay,
+                frames: vec![],
+            }),
+            Backend::Gif => Box::new(Gif {
+                path,
+                delay,
+                frames: vec![],
+            }),
+            Backend::Png => Box::new(Png {
+                dir: path,
+                frames: vec![],
+            }),
+        }
+    }
+}
+
+/// Redraws the screen with every frame as it comes, so the simulation runs at the speed of the animation.
+pub struct Terminal {
+    pub delay: Duration,
+}
+
+impl Recorder for Terminal {
+    fn record(&mut self, frame: Frame) {
+        // clear the screen and go to its top left corner
+        print!("\x1b[2J\x1b[H{frame}");
+        let _ = io::stdout().flush();
+        thread::sleep(self.delay);
+    }
+}
+
+/// Collects the frames and writes them as an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) file.
+pub struct Asciicast {
+    pub path: PathBuf,
+    pub delay: Duration,
+    pub frames: Vec<Frame>,
+}
+
+impl Recorder for Asciicast {
+    fn record(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let (width, height) = size(&self.frames);
+        let mut out = BufWriter::new(create(&self.path)?);
+
+        // one more line for the caption
+        writeln!(out, "{}", json!({"version": 2, "width": width, "height": height + 1}))?;
+        for (n, frame) in self.frames.iter().enumerate() {
+            let time = (self.delay * n as u32).as_secs_f64();
+            let screen = format!("\x1b[2J\x1b[H{}", frame.to_string().replace('\n', "\r\n"));
+            writeln!(out, "{}", json!([time, "o", screen]))?;
+        }
+
+        Ok(out.flush()?)
+    }
+}
+
+/// Collects the frames and writes them as an animated GIF, every character a square of colour.
+pub struct Gif {
+    pub path: PathBuf,
+    pub delay: Duration,
+    pub frames: Vec<Frame>,
+}
+
+impl Recorder for Gif {
+    fn record(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let (width, height) = size(&self.frames);
+        let scale = scale(width, height);
+        let (width, height) = (width * scale, height * scale);
+
+        let mut encoder = gif::Encoder::new(
+            BufWriter::new(create(&self.path)?),
+            width as u16,
+            height as u16,
+            &palette(),
+        )?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        for frame in &self.frames {
+            let frame = gif::Frame {
+                width: width as u16,
+                height: height as u16,
+                // in hundredths of a second
+                delay: (self.delay.as_millis() / 10) as u16,
+                buffer: Cow::Owned(pixels(frame, width, height, scale)),
+                ..Default::default()
+            };
+            encoder.write_frame(&frame)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the frames and writes each of them as a PNG image into a directory.
+pub struct Png {
+    pub dir: PathBuf,
+    pub frames: Vec<Frame>,
+}
+
+impl Recorder for Png {
+    fn record(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let (width, height) = size(&self.frames);
+        let scale = scale(width, height);
+        let (width, height) = (width * scale, height * scale);
+
+        fs::create_dir_all(&self.dir)?;
+        for (n, frame) in self.frames.iter().enumerate() {
+            let file = BufWriter::new(File::create(self.dir.join(format!("frame-{n:05}.png")))?);
+            let mut encoder = png::Encoder::new(file, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(palette());
+
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&pixels(frame, width, height, scale))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn create(path: &Path) -> Result<File> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    Ok(File::create(path)?)
+}
+
+/// The frames can differ in size, the animation is as big as the biggest one.
+fn size(frames: &[Frame]) -> (usize, usize) {
+    let width = frames.iter().map(Frame::width).max().unwrap_or_default();
+    let height = frames.iter().map(Frame::height).max().unwrap_or_default();
+    (width.max(1), height.max(1))
+}
+
+/// How many pixels a character gets, so that the image comes out about 800 pixels across.
+fn scale(width: usize, height: usize) -> usize {
+    (800 / width.max(height)).clamp(1, 8)
+}
+
+/// A colour for every ASCII character, indexed by its code; anything else is drawn like `?`.
+fn palette() -> Vec<u8> {
+    (0..=127u8).flat_map(|c| colour(c as char)).collect()
+}
+
+fn colour(c: char) -> [u8; 3] {
+    match c {
+        '.' | ' ' => [16, 16, 35],
+        '#' => [204, 204, 204],
+        'o' => [230, 190, 90],
+        '+' | 'H' => [230, 60, 60],
+        'S' | 'E' => [255, 140, 0],
+        '<' | '>' | '^' | 'v' => [255, 255, 102],
+        '[' | ']' => [120, 80, 40],
+        // the knots of the rope, fading towards the tail
+        '0'..='9' | 'T' => {
+            let n = c.to_digit(10).unwrap_or(9) as u8;
+            [230 - n * 15, 100 + n * 10, 60 + n * 15]
+        }
+        // crates
+        'A'..='Z' => [200, 140, 70],
+        // heights, from dark to bright
+        'a'..='z' => {
+            let n = c as u8 - b'a';
+            [20, 60 + n * 7, 30]
+        }
+        _ => [255, 255, 255],
+    }
+}
+
+/// The frame as palette indices, row by row, padded with the background to `width`×`height` pixels.
+fn pixels(frame: &Frame, width: usize, height: usize, scale: usize) -> Vec<u8> {
+    let mut pixels = vec![b'.'; width * height];
+
+    for (y, line) in frame.lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let index = if c.is_ascii() { c as u8 } else { b'?' };
+            for py in y * scale..(y + 1) * scale {
+                pixels[py * width + x * scale..py * width + (x + 1) * scale].fill(index);
+            }
+        }
+    }
+
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<Frame> {
+        vec![Frame::new("#.\n.#").with_caption("one"), Frame::new("o")]
+    }
+
+    #[test]
+    fn test_pixels() {
+        let frame = Frame::new("#o\n.");
+        assert_eq!(pixels(&frame, 4, 4, 2), b"##oo##oo........".to_vec());
+        assert_eq!(pixels(&frame, 3, 3, 1), b"#o.......".to_vec());
+        assert_eq!(palette().len(), 128 * 3);
+    }
+
+    #[test]
+    fn test_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-visualize-{}", std::process::id()));
+
+        for backend in [Backend::Asciicast, Backend::Gif, Backend::Png] {
+            let path = backend.path(&dir, 2022, 14, 1).unwrap();
+            let mut recorder = backend.recorder(Some(path.clone()), Duration::from_millis(50));
+            frames().into_iter().for_each(|f| recorder.record(f));
+            recorder.finish().unwrap();
+            assert!(path.exists(), "{path:?}");
+        }
+
+        let cast = fs::read_to_string(dir.join("2022-day-14-part1.cast")).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines[0], r#"{"height":3,"version":2,"width":2}"#);
+        assert_eq!(lines[2], r#"[0.05,"o","\u001b[2J\u001b[Ho\r\n"]"#);
+
+        let gif = fs::read(dir.join("2022-day-14-part1.gif")).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert!(dir.join("2022-day-14-part1/frame-00001.png").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
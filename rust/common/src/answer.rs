@@ -0,0 +1,93 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// Multi-line output meant to be read by a human, like the CRT letters of 2022 day 10.
+    Art(String),
+}
+
+impl Answer {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Answer::Number(_) => false,
+            Answer::Text(s) | Answer::Art(s) => s.is_empty(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) | Answer::Art(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Most days already stringify their result, so numbers and art are recovered from the text.
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        if s.contains('\n') {
+            return Answer::Art(s);
+        }
+
+        // only text that reads back the same, "007" keeps its zeros
+        match s.parse::<i64>() {
+            Ok(n) if n.to_string() == s => Answer::Number(n),
+            _ => Answer::Text(s),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        s.to_string().into()
+    }
+}
+
+macro_rules! from_number {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Answer {
+                /// Numbers too big for an i64 are kept as their digits.
+                fn from(n: $t) -> Self {
+                    i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+                }
+            }
+        )*
+    };
+}
+
+from_number!(i32, u32, i64, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(Answer::from("24000"), Answer::Number(24000));
+        assert_eq!(Answer::from("-3"), Answer::Number(-3));
+        assert_eq!(Answer::from("CMZ"), Answer::Text("CMZ".to_string()));
+        assert_eq!(Answer::from("#.\n.#"), Answer::Art("#.\n.#".to_string()));
+        assert_eq!(Answer::from(""), Answer::Text("".to_string()));
+        assert_eq!(Answer::from("007"), Answer::Text("007".to_string()));
+        assert_eq!(Answer::from("+5"), Answer::Text("+5".to_string()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(45000u32).to_string(), "45000");
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(Answer::from("#.\n.#").to_string(), "#.\n.#");
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(Answer::from("").is_empty());
+        assert!(!Answer::from(0u32).is_empty());
+    }
+}
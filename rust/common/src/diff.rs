@@ -0,0 +1,82 @@
+//! Differential testing, for days carrying two implementations of the same thing.
+//!
+//! Both implementations are turned into iterators over the states they go through, one per step,
+//! and [`first_divergence`] runs them side by side until they disagree.
+
+use std::fmt::{self, Debug, Display};
+
+/// The first step after which two implementations were in different states.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence<S> {
+    pub step: usize,
+    /// What the step was, for the report.
+    pub label: String,
+    /// `None` when that implementation had already finished.
+    pub left: Option<S>,
+    pub right: Option<S>,
+}
+
+impl<S> Divergence<S> {
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+}
+
+impl<S: Debug> Display for Divergence<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "diverged at step {}", self.step)?;
+        if !self.label.is_empty() {
+            write!(f, " ({})", self.label)?;
+        }
+
+        let state = |s: &Option<S>| s.as_ref().map_or("finished".to_string(), |s| format!("{s:?}"));
+        write!(f, "\n  left:  {}\n  right: {}", state(&self.left), state(&self.right))
+    }
+}
+
+/// Steps both implementations in lockstep and returns where their states first differ, `None` if they never do.
+/// Ending early counts as differing, so a step that is missing on one side gets reported too.
+pub fn first_divergence<S: PartialEq>(
+    left: impl IntoIterator<Item = S>,
+    right: impl IntoIterator<Item = S>,
+) -> Option<Divergence<S>> {
+    let (mut left, mut right) = (left.into_iter(), right.into_iter());
+
+    for step in 0.. {
+        match (left.next(), right.next()) {
+            (None, None) => return None,
+            (l, r) if l != r => {
+                return Some(Divergence {
+                    step,
+                    label: String::new(),
+                    left: l,
+                    right: r,
+                })
+            }
+            _ => {}
+        }
+    }
+
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_divergence() {
+        assert_eq!(first_divergence([1, 2, 3], [1, 2, 3]), None);
+
+        let d = first_divergence([1, 2, 3], [1, 5, 3]).unwrap();
+        assert_eq!((d.step, d.left, d.right), (1, Some(2), Some(5)));
+
+        let d = first_divergence([1, 2], [1, 2, 3]).unwrap().with_label("third");
+        assert_eq!((d.step, d.left, d.right), (2, None, Some(3)));
+        assert_eq!(
+            d.to_string(),
+            "diverged at step 2 (third)\n  left:  finished\n  right: 3"
+        );
+    }
+}
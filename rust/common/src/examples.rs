@@ -0,0 +1,198 @@
+//! The examples of a puzzle, with their expected answers, declared in the day's `examples.toml`:
+//!
+//! ```toml
+//! [[example]]
+//! name = "example"           # the tests are called `example_part1` and `example_part2`
+//! input = "test_input.txt"   # a file next to the manifest...
+//! part1 = 26
+//! part2 = 56000011
+//! params = { row = 10 }      # see `params`, these override the input's table in `params.toml`
+//! ignore = [2]               # parts that do not work yet
+//!
+//! [[example]]
+//! name = "short"
+//! text = "R 4\nU 4"          # ...or the input itself
+//! part1 = "13"
+//! ```
+//!
+//! `aoc_common::examples!(Solver)` in the day's tests generates a test for every part with an answer.
+
+use std::{fs, path::Path};
+
+use toml::{Table, Value};
+
+use crate::{eyre::eyre, Params, Result, Solution};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    /// A file relative to the manifest.
+    File(String),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: Input,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub params: Params,
+    pub ignore: Vec<u8>,
+}
+
+impl Example {
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn source(&self, dir: &Path) -> Result<String> {
+        match &self.input {
+            Input::File(file) => fs::read_to_string(dir.join(file)).map_err(|e| eyre!("Cannot read {file}: {e}")),
+            Input::Text(text) => Ok(text.clone()),
+        }
+    }
+
+    /// The input's own parameters, overridden by the example's.
+    pub fn params(&self, dir: &Path) -> Result<Params> {
+        let mut params = match &self.input {
+            Input::File(file) => Params::load(dir, &dir.join(file))?,
+            Input::Text(_) => Params::new(),
+        };
+        params.merge(self.params.clone());
+
+        Ok(params)
+    }
+}
+
+/// The examples of the day in `dir`, none if it has no manifest.
+pub fn load(dir: &Path) -> Result<Vec<Example>> {
+    let path = dir.join("examples.toml");
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    parse(&fs::read_to_string(path)?)
+}
+
+pub fn parse(source: &str) -> Result<Vec<Example>> {
+    let mut manifest: Table = toml::from_str(source)?;
+    let Some(examples) = manifest.remove("example") else {
+        return Ok(vec![]);
+    };
+
+    let Value::Array(examples) = examples else {
+        return Err(eyre!("`example` must be an array of tables, i.e. `[[example]]`!"));
+    };
+
+    examples.into_iter().map(example).collect()
+}
+
+fn example(value: Value) -> Result<Example> {
+    let Value::Table(mut t) = value else {
+        return Err(eyre!("Every example must be a table!"));
+    };
+
+    let name = match t.remove("name") {
+        Some(Value::String(name)) => name,
+        _ => return Err(eyre!("Every example needs a `name`!")),
+    };
+
+    let input = match (t.remove("input"), t.remove("text")) {
+        (Some(Value::String(file)), None) => Input::File(file),
+        (None, Some(Value::String(text))) => Input::Text(text),
+        _ => return Err(eyre!("The example {name:?} needs either an `input` file or a `text`!")),
+    };
+
+    let answer = |v: Option<Value>| match v {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(Value::Integer(n)) => Ok(Some(n.to_string())),
+        Some(v) => Err(eyre!("The answers of {name:?} must be strings or numbers, got {v}!")),
+    };
+    let part1 = answer(t.remove("part1"))?;
+    let part2 = answer(t.remove("part2"))?;
+
+    let mut params = Params::new();
+    if let Some(Value::Table(p)) = t.remove("params") {
+        for (k, v) in p {
+            let v = v
+                .as_integer()
+                .ok_or_else(|| eyre!("The parameter `{k}` of {name:?} must be a number!"))?;
+            params.set(&k, v);
+        }
+    }
+
+    let ignore = match t.remove("ignore") {
+        None => vec![],
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(|p| p.as_integer().and_then(|p| u8::try_from(p).ok()))
+            .collect::<Option<_>>()
+            .ok_or_else(|| eyre!("The `ignore` of {name:?} must be a list of parts!"))?,
+        Some(_) => return Err(eyre!("The `ignore` of {name:?} must be a list of parts!")),
+    };
+
+    if let Some(key) = t.keys().next() {
+        return Err(eyre!("Unknown key `{key}` in the example {name:?}!"));
+    }
+
+    Ok(Example {
+        name,
+        input,
+        part1,
+        part2,
+        params,
+        ignore,
+    })
+}
+
+/// Runs one part of the example `name` of the day in `dir` and asserts its answer, for the generated tests.
+pub fn check(solution: &dyn Solution, dir: &str, name: &str, part: u8) {
+    let dir = Path::new(dir);
+    let examples = load(dir).unwrap();
+    let example = examples
+        .iter()
+        .find(|e| e.name == name)
+        .unwrap_or_else(|| panic!("There is no example {name:?}!"));
+
+    let source = example.source(dir).unwrap();
+    let params = example.params(dir).unwrap();
+    let answer = solution.part_with(part, &source, &params).unwrap().to_string();
+
+    // art is compared without the newline the files tend to end with
+    let expected = example.answer(part).unwrap_or_default();
+    assert_eq!(answer.trim_end(), expected.trim_end(), "{name}, part {part}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let source = "[[example]]\nname = \"example\"\ninput = \"test_input.txt\"\npart1 = 26\nparams = { row = 10 }\nignore = [2]\n\n[[example]]\nname = \"short\"\ntext = \"abc\"\npart2 = \"CMZ\"\n";
+        let examples = parse(source).unwrap();
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, Input::File("test_input.txt".to_string()));
+        assert_eq!(examples[0].answer(1), Some("26"));
+        assert_eq!(examples[0].answer(2), None);
+        assert_eq!(examples[0].params, Params::new().with("row", 10));
+        assert_eq!(examples[0].ignore, vec![2]);
+        assert_eq!(examples[1].source(Path::new(".")).unwrap(), "abc");
+        assert_eq!(examples[1].answer(2), Some("CMZ"));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(parse("[[example]]\ntext = \"abc\"\n").is_err());
+        assert!(parse("[[example]]\nname = \"a\"\n").is_err());
+        assert!(parse("[[example]]\nname = \"a\"\ntext = \"abc\"\npart3 = 1\n").is_err());
+        assert!(parse("[[example]]\nname = \"a\"\ntext = \"abc\"\nignore = 2\n").is_err());
+        assert!(parse("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+//! Pictures of a simulation while it runs, for the days whose puzzle is worth watching.
+//!
+//! A day draws its state as text, the way the puzzle itself does, and hands every picture to a [`Recorder`].
+//! Where the frames end up (the terminal, a file, a web page) is up to the recorder.
+
+use std::fmt;
+
+use crate::Result;
+
+/// One picture, lines of characters plus a caption saying what happened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub lines: Vec<String>,
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(picture: &str) -> Self {
+        Self {
+            lines: picture.lines().map(str::to_string).collect(),
+            caption: String::new(),
+        }
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// In characters, of the longest line.
+    pub fn width(&self) -> usize {
+        self.lines.iter().map(|l| l.chars().count()).max().unwrap_or_default()
+    }
+
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{line}")?;
+        }
+        if !self.caption.is_empty() {
+            writeln!(f, "{}", self.caption)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Where a simulation sends its frames.
+pub trait Recorder {
+    fn record(&mut self, frame: Frame);
+
+    /// Called once the simulation is over, for recorders that write everything out at the end.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Keeps all the frames in memory.
+#[derive(Debug, Default)]
+pub struct Frames(pub Vec<Frame>);
+
+impl Recorder for Frames {
+    fn record(&mut self, frame: Frame) {
+        self.0.push(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame() {
+        let frame = Frame::new("..#\n#\n").with_caption("step 1");
+        assert_eq!((frame.width(), frame.height()), (3, 2));
+        assert_eq!(frame.to_string(), "..#\n#\nstep 1\n");
+
+        let mut frames = Frames::default();
+        frames.record(frame.clone());
+        frames.finish().unwrap();
+        assert_eq!(frames.0, vec![frame]);
+    }
+}
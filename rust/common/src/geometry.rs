@@ -0,0 +1,417 @@
+//! Points, vectors and directions shared by the grid-ish days.
+//!
+//! `y` grows downwards, the same way the lines of a puzzle input do, so [`Direction::Up`] is `(0, -1)`.
+
+use std::{
+    fmt,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// The signed numbers coordinates can be made of.
+pub trait Scalar:
+    Copy
+    + Default
+    + Ord
+    + Hash
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! scalar {
+    ($($t:ty),*) => {
+        $(impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        })*
+    };
+}
+
+scalar!(i8, i16, i32, i64, isize);
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// The difference between two [`Point2`]s.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// The difference between two [`Point3`]s.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Scalar> Point2<T> {
+    pub fn origin() -> Self {
+        Self::new(T::ZERO, T::ZERO)
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        (*self - *other).manhattan_len()
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        (*self - *other).chebyshev_len()
+    }
+}
+
+impl<T: Scalar> Point3<T> {
+    pub fn origin() -> Self {
+        Self::new(T::ZERO, T::ZERO, T::ZERO)
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        (*self - *other).manhattan_len()
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        (*self - *other).chebyshev_len()
+    }
+}
+
+impl<T: Scalar> Vec2<T> {
+    pub fn manhattan_len(&self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev_len(&self) -> T {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// Shrinks every component to `-1`, `0` or `1`, i.e. a single step in the same general direction.
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Rotates by 90° counter-clockwise, as seen on the screen.
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Rotates by 90° clockwise, as seen on the screen.
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+impl<T: Scalar> Vec3<T> {
+    pub fn manhattan_len(&self) -> T {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn chebyshev_len(&self) -> T {
+        self.x.abs().max(self.y.abs()).max(self.z.abs())
+    }
+
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+macro_rules! ops {
+    ($point:ident, $vec:ident, $($c:ident),+) => {
+        impl<T: Scalar> Sub for $point<T> {
+            type Output = $vec<T>;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $vec::new($(self.$c - rhs.$c),+)
+            }
+        }
+
+        impl<T: Scalar> Add<$vec<T>> for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: $vec<T>) -> Self::Output {
+                Self::new($(self.$c + rhs.$c),+)
+            }
+        }
+
+        impl<T: Scalar> Sub<$vec<T>> for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: $vec<T>) -> Self::Output {
+                Self::new($(self.$c - rhs.$c),+)
+            }
+        }
+
+        impl<T: Scalar> AddAssign<$vec<T>> for $point<T> {
+            fn add_assign(&mut self, rhs: $vec<T>) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Scalar> SubAssign<$vec<T>> for $point<T> {
+            fn sub_assign(&mut self, rhs: $vec<T>) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T: Scalar> Add for $vec<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self::new($(self.$c + rhs.$c),+)
+            }
+        }
+
+        impl<T: Scalar> Sub for $vec<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self::new($(self.$c - rhs.$c),+)
+            }
+        }
+
+        impl<T: Scalar> Mul<T> for $vec<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self::new($(self.$c * rhs),+)
+            }
+        }
+
+        impl<T: Scalar> Neg for $vec<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self::new($(-self.$c),+)
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $point<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple("")$(.field(&self.$c))+.finish()
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $vec<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple("")$(.field(&self.$c))+.finish()
+            }
+        }
+    };
+}
+
+ops!(Point2, Vec2, x, y);
+ops!(Point3, Vec3, x, y, z);
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+/// Indices into the input, as `(column, line)`.
+impl From<(usize, usize)> for Point2<isize> {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from the top.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// Accepts both the `UDLR` letters and the `^v<>` arrows.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | '^' => Some(Self::Up),
+            'R' | '>' => Some(Self::Right),
+            'D' | 'v' => Some(Self::Down),
+            'L' | '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub fn vector<T: Scalar>(&self) -> Vec2<T> {
+        match self {
+            Self::Up => Vec2::new(T::ZERO, -T::ONE),
+            Self::Right => Vec2::new(T::ONE, T::ZERO),
+            Self::Down => Vec2::new(T::ZERO, T::ONE),
+            Self::Left => Vec2::new(-T::ONE, T::ZERO),
+        }
+    }
+
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(*self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 4]
+    }
+
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(*self as usize + 2) % 4]
+    }
+}
+
+/// The eight directions of a compass, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Clockwise, starting from the north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    pub fn vector<T: Scalar>(&self) -> Vec2<T> {
+        let (o, i) = (T::ZERO, T::ONE);
+        let (x, y) = match self {
+            Self::N => (o, -i),
+            Self::NE => (i, -i),
+            Self::E => (i, o),
+            Self::SE => (i, i),
+            Self::S => (o, i),
+            Self::SW => (-i, i),
+            Self::W => (-i, o),
+            Self::NW => (-i, -i),
+        };
+        Vec2::new(x, y)
+    }
+
+    /// Turns by 45° clockwise `steps` times, negative steps turn the other way.
+    pub fn rotate(&self, steps: i32) -> Self {
+        Self::ALL[(*self as i32 + steps).rem_euclid(8) as usize]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        Direction8::ALL[d as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(1, 5);
+        let b = Point2::new(4, -1);
+
+        assert_eq!(b - a, Vec2::new(3, -6));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(-(b - a) * 2, Vec2::new(-6, 12));
+        assert_eq!((b - a).signum(), Vec2::new(1, -1));
+
+        let c = Point3::new(1, 2, 3) - Point3::new(3, 2, 1);
+        assert_eq!(c, Vec3::new(-2, 0, 2));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new(1, 5);
+        let b = Point2::new(4, -1);
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(a.chebyshev(&b), 6);
+
+        let c = Point3::<i64>::origin();
+        assert_eq!(c.manhattan(&Point3::new(-1, 2, -3)), 6);
+        assert_eq!(c.chebyshev(&Point3::new(-1, 2, -3)), 3);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let v = Direction::Up.vector::<i32>();
+        assert_eq!(v.rotate_right(), Direction::Right.vector());
+        assert_eq!(v.rotate_left(), Direction::Left.vector());
+        assert_eq!(v.rotate_left().rotate_left(), Direction::Down.vector());
+
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.opposite(), Direction::Left);
+
+        assert_eq!(Direction8::N.rotate(-1), Direction8::NW);
+        assert_eq!(Direction8::from(Direction::Down), Direction8::S);
+        assert_eq!(Direction8::NE.vector::<i32>(), Vec2::new(1, -1));
+    }
+}
@@ -0,0 +1,72 @@
+mod answer;
+pub mod diff;
+pub mod examples;
+pub mod frames;
+pub mod geometry;
+pub mod log;
+pub mod params;
+pub mod parse;
+
+pub use answer::Answer;
+pub use aoc_macros::examples;
+pub use color_eyre::{eyre, Result};
+pub use frames::{Frame, Recorder};
+pub use params::Params;
+pub use rand;
+pub use tracing;
+
+/// A single day's puzzle, implemented by every day crate so tooling can treat them all the same way.
+pub trait Solution: Send + Sync {
+    fn part1(&self, input: &str) -> Result<Answer>;
+    fn part2(&self, input: &str) -> Result<Answer>;
+
+    /// Runs only the input parsing, for days where that is a separate step worth measuring on its own.
+    /// `None` means the day parses inside each part.
+    fn parse(&self, _input: &str) -> Option<Result<()>> {
+        None
+    }
+
+    /// A random but valid puzzle input, for stress tests and benchmarks at scale.
+    /// What `size` counts (lines, monkeys, packets...) is up to the day, `None` means it has no generator.
+    fn generate(&self, _seed: u64, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Runs a part while drawing what the simulation does into `recorder`.
+    /// `None` means there is nothing to watch in that part.
+    fn visualize(&self, _part: u8, _input: &str, _recorder: &mut dyn Recorder) -> Option<Result<Answer>> {
+        None
+    }
+
+    /// Like [`part1`](Self::part1), for days whose puzzle has [`Params`] besides the input.
+    fn part1_with(&self, input: &str, _params: &Params) -> Result<Answer> {
+        self.part1(input)
+    }
+
+    fn part2_with(&self, input: &str, _params: &Params) -> Result<Answer> {
+        self.part2(input)
+    }
+
+    /// Like [`visualize`](Self::visualize), for a day with both [`Params`] and something to watch.
+    fn visualize_with(
+        &self,
+        part: u8,
+        input: &str,
+        _params: &Params,
+        recorder: &mut dyn Recorder,
+    ) -> Option<Result<Answer>> {
+        self.visualize(part, input, recorder)
+    }
+
+    fn part(&self, part: u8, input: &str) -> Result<Answer> {
+        self.part_with(part, input, &Params::new())
+    }
+
+    fn part_with(&self, part: u8, input: &str, params: &Params) -> Result<Answer> {
+        match part {
+            1 => self.part1_with(input, params),
+            2 => self.part2_with(input, params),
+            _ => Err(eyre::eyre!("There is no part {part}!")),
+        }
+    }
+}
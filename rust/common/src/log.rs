@@ -0,0 +1,31 @@
+//! Diagnostic output of the solutions, silent unless asked for.
+//!
+//! Days log through the [`tracing`](crate::tracing) macros, their target being the crate name, e.g. `aoc_2022_day_11`.
+//! Everything goes to stderr, so the answers on stdout stay clean.
+
+use tracing_subscriber::{filter::LevelFilter, EnvFilter};
+
+/// Sets up the logging for a binary: `RUST_LOG` wins if set (`RUST_LOG=aoc_2022_day_11=debug`),
+/// otherwise `verbosity` picks the level: nothing, then info, debug and trace for every `-v`.
+pub fn init(verbosity: u8) {
+    let filter = match std::env::var("RUST_LOG") {
+        Ok(directives) if !directives.is_empty() => EnvFilter::new(directives),
+        _ => EnvFilter::default().add_directive(level(verbosity).into()),
+    };
+
+    // a second initialisation (e.g. from tests) keeps the first one
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .without_time()
+        .try_init();
+}
+
+fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
@@ -0,0 +1,146 @@
+//! Values a puzzle states in its text rather than in the input, like the row to look at in 2022 day 15.
+//!
+//! A value every real input shares is best kept as a default in the day's code, with params only overriding it.
+//! The others live in the day's `params.toml`, with one table per input file.
+//! Any input that is not one of the day's examples uses the `[real_input]` table, wherever the file lives:
+//!
+//! ```toml
+//! [real_input]
+//! noun = 12
+//! verb = 2
+//! ```
+//!
+//! The examples declare theirs in `examples.toml` instead, see [`examples`](crate::examples).
+
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+use crate::{examples::Input, eyre::eyre, Result};
+
+/// The table every input that is not an example falls back to.
+const REAL_INPUT: &str = "real_input";
+
+type Tables = BTreeMap<String, BTreeMap<String, i64>>;
+
+/// The parameters of a single input, see the [module docs](self).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, i64>);
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, value: i64) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.0.insert(name.to_string(), value);
+    }
+
+    /// For values only some of the inputs have.
+    pub fn optional(&self, name: &str) -> Option<i64> {
+        self.0.get(name).copied()
+    }
+
+    pub fn get(&self, name: &str) -> Result<i64> {
+        self.0.get(name).copied().ok_or_else(|| {
+            eyre!(
+                "The `{name}` parameter is missing, add it to the day's params.toml or pass `--param {name}=<value>`!"
+            )
+        })
+    }
+
+    /// The parameters of `input` for the day in `dir`: the table named after the file in the day's `params.toml`,
+    /// along with those of the first example reading it.
+    /// Any other input is taken for a real one, wherever it lives, and gets the `[real_input]` table.
+    pub fn load(dir: &Path, input: &Path) -> Result<Self> {
+        let path = dir.join("params.toml");
+        let mut tables = if path.exists() {
+            toml::from_str(&fs::read_to_string(path)?)?
+        } else {
+            Tables::new()
+        };
+
+        // a file elsewhere cannot be one of the day's examples
+        let parent = input
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let same_dir = matches!((fs::canonicalize(parent), fs::canonicalize(dir)), (Ok(a), Ok(b)) if a == b);
+        let Some(name) = input.file_stem().and_then(|s| s.to_str()).filter(|_| same_dir) else {
+            return Ok(Self(tables.remove(REAL_INPUT).unwrap_or_default()));
+        };
+        let file = input.file_name().and_then(|s| s.to_str()).unwrap_or_default();
+
+        let examples = crate::examples::load(dir)?;
+        let params = match examples.into_iter().find(|e| e.input == Input::File(file.to_string())) {
+            Some(e) => {
+                let mut params = Self(tables.remove(name).unwrap_or_default());
+                params.merge(e.params);
+                params
+            }
+            None => Self(
+                tables
+                    .remove(name)
+                    .or_else(|| tables.remove(REAL_INPUT))
+                    .unwrap_or_default(),
+            ),
+        };
+
+        Ok(params)
+    }
+
+    /// The `table` of a `params.toml`.
+    pub fn parse(source: &str, table: &str) -> Result<Self> {
+        let mut tables: Tables = toml::from_str(source)?;
+        Ok(Self(tables.remove(table).unwrap_or_default()))
+    }
+
+    /// Overrides the loaded values with `other`.
+    pub fn merge(&mut self, other: Params) {
+        self.0.extend(other.0);
+    }
+}
+
+/// A single `name=value` pair, as given on the command line.
+impl FromStr for Params {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, value) = s
+            .split_once('=')
+            .ok_or_else(|| eyre!("Expected `name=value`, got {s:?}!"))?;
+
+        Ok(Self::new().with(name.trim(), value.trim().parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let source = "[real_input]\nrow = 2000000\n\n[test_input]\nrow = 10\n";
+
+        assert_eq!(Params::parse(source, "test_input").unwrap().get("row").unwrap(), 10);
+        assert_eq!(
+            Params::parse(source, "real_input").unwrap(),
+            Params::new().with("row", 2000000)
+        );
+        assert_eq!(Params::parse(source, "other").unwrap(), Params::new());
+        assert!(Params::new().get("row").is_err());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut p = Params::parse("[real_input]\nrow = 2000000\nmax = 5\n", "real_input").unwrap();
+        p.merge("row = 10".parse().unwrap());
+
+        assert_eq!(p, Params::new().with("row", 10).with("max", 5));
+        assert!("row".parse::<Params>().is_err());
+        assert!("row=x".parse::<Params>().is_err());
+    }
+}
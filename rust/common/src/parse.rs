@@ -0,0 +1,237 @@
+//! A nom error type that remembers what was expected where, and turns into a readable report.
+//!
+//! Parsers use [`IResult`] instead of nom's own, wrap their literals in [`token`] and hand the result to [`finish`].
+//! Lists should `cut` their items, otherwise a broken item just ends the list and the error points at its end.
+
+use std::fmt;
+
+use color_eyre::{Section, SectionExt};
+use nom::{
+    bytes::complete::tag,
+    error::{ContextError, ErrorKind, FromExternalError, ParseError as NomParseError},
+};
+
+use crate::Result;
+
+pub type IResult<'a, T> = nom::IResult<&'a str, T, Error<'a>>;
+
+/// What a parser was looking for when it failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Kind(ErrorKind),
+    Char(char),
+    Token(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Kind(ErrorKind::Digit) => write!(f, "a number"),
+            Expected::Kind(ErrorKind::Alpha) => write!(f, "a letter"),
+            Expected::Kind(ErrorKind::Eof) => write!(f, "the end of the input"),
+            Expected::Kind(ErrorKind::OneOf) => write!(f, "one of the allowed characters"),
+            Expected::Kind(ErrorKind::MapOpt | ErrorKind::MapRes | ErrorKind::Verify) => write!(f, "a valid value"),
+            Expected::Kind(kind) => write!(f, "{}", kind.description().to_lowercase()),
+            Expected::Char(c) => write!(f, "{c:?}"),
+            Expected::Token(t) => write!(f, "{t:?}"),
+        }
+    }
+}
+
+/// The nom error of the parsers, `input` is what was left when the innermost parser failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error<'a> {
+    pub input: &'a str,
+    pub expected: Expected,
+}
+
+impl<'a> NomParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: Expected::Kind(kind),
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        // the innermost failure is the one worth reporting
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Self {
+            input,
+            expected: Expected::Char(c),
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        // of all the alternatives, the one that got furthest is likely the intended one
+        if other.input.len() <= self.input.len() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(input: &'a str, ctx: &'static str, mut other: Self) -> Self {
+        if input == other.input {
+            other.expected = Expected::Token(ctx);
+        }
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _e: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+/// Like nom's `tag`, but a failure reports the literal that was expected.
+pub fn token<'a>(t: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |i| {
+        tag(t)(i).map_err(|e: nom::Err<Error<'a>>| {
+            e.map(|mut e| {
+                e.expected = Expected::Token(t);
+                e
+            })
+        })
+    }
+}
+
+/// A parse failure, located within the whole puzzle input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// The offending line of the input.
+    pub text: String,
+}
+
+impl ParseError {
+    /// Locates `error` in `source`, which the parsed input must be a part of.
+    pub fn new(source: &str, error: &Error) -> Self {
+        let offset = (error.input.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|&o| o <= source.len())
+            .unwrap_or(0);
+
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..].find('\n').map_or(source.len(), |i| offset + i);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: error.expected.to_string(),
+            text: source[line_start..line_end].to_string(),
+        }
+    }
+
+    /// The offending line with a caret under the place of the failure.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+
+        format!(
+            "{pad} |\n{number} | {}\n{pad} | {}^ expected {}",
+            self.text,
+            " ".repeat(self.column - 1),
+            self.expected
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected {} at line {}, column {}",
+            self.expected, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Turns the result of parsing (a part of) `source` into the parsed value or a located error report.
+pub fn finish<'a, T>(source: &'a str, res: IResult<'a, T>) -> Result<T> {
+    match res {
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let error = ParseError::new(source, &e);
+            let snippet = error.snippet();
+            Err(color_eyre::Report::new(error).section(snippet.header("Input:")))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(crate::eyre::eyre!("The input ended too early!")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        character::complete::u32,
+        combinator::{all_consuming, cut},
+        multi::separated_list1,
+        sequence::preceded,
+    };
+    use proptest::prelude::*;
+
+    fn numbers(i: &str) -> IResult<'_, Vec<u32>> {
+        // without the `cut`, the list would quietly end before the broken number
+        all_consuming(separated_list1(token(", "), cut(preceded(token("n="), u32))))(i)
+    }
+
+    fn error(source: &str) -> ParseError {
+        let e = finish(source, numbers(source)).unwrap_err();
+        e.downcast_ref::<ParseError>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_located() {
+        assert_eq!(finish("n=1, n=2", numbers("n=1, n=2")).unwrap(), vec![1, 2]);
+
+        let e = error("n=1, n=x");
+        assert_eq!((e.line, e.column), (1, 8));
+        assert_eq!(e.expected, "a number");
+        assert_eq!(e.to_string(), "Expected a number at line 1, column 8");
+        assert_eq!(e.snippet(), "  |\n1 | n=1, n=x\n  |        ^ expected a number");
+
+        let e = error("n=1, n=2\nm");
+        assert_eq!((e.line, e.column), (1, 9));
+        assert_eq!(e.expected, "the end of the input");
+    }
+
+    #[test]
+    fn test_token() {
+        let e = error("n=1, m=2");
+        assert_eq!((e.line, e.column), (1, 6));
+        assert_eq!(e.expected, "\"n=\"");
+    }
+
+    #[test]
+    fn test_part_of_source() {
+        let source = "n=1\nn=2\nn=3, n";
+        let last = source.lines().last().unwrap();
+        let e = finish(source, numbers(last)).unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 6, "n=3, n"));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_located(source in "(n=[0-9]{1,3}|[n=, x\n]|\\PC){0,30}") {
+            if let Err(e) = finish(&source, numbers(&source)) {
+                let e = e.downcast_ref::<ParseError>().unwrap();
+                // the caret always lands on the reported line, at most just past its end
+                prop_assert_eq!(source.split('\n').nth(e.line - 1), Some(e.text.as_str()));
+                prop_assert!(e.column <= e.text.chars().count() + 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+pub use aoc_common::geometry::{Direction, Direction8, Point2, Vec2};
+
+use aoc_common::{eyre::eyre, Result};
+use std::{collections::BTreeMap, fmt, ops};
+
+/// A cell of a [`Grid`], `x` grows to the right and `y` grows downwards, like the lines of the input.
+pub type Pos = Point2<isize>;
+
+/// A rectangular 2D grid, stored row by row.
+///
+/// The top-left cell does not have to be `(0, 0)`: the grid can start at negative coordinates and grow in any direction.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    origin: Pos,
+}
+
+/// Positions of the special cells (like `S` and `E`) found while parsing, in reading order.
+#[derive(Debug, Default, PartialEq)]
+pub struct Marks(BTreeMap<char, Vec<Pos>>);
+
+impl Marks {
+    /// The first position of `c`.
+    pub fn get(&self, c: char) -> Option<Pos> {
+        self.all(c).first().copied()
+    }
+
+    pub fn all(&self, c: char) -> &[Pos] {
+        self.0.get(&c).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::with_bounds(Pos::new(0, 0), Pos::new(width as isize - 1, height as isize - 1), fill)
+    }
+
+    /// A grid covering `min..=max`.
+    pub fn with_bounds(min: Pos, max: Pos, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let width = (max.x - min.x + 1).max(0) as usize;
+        let height = (max.y - min.y + 1).max(0) as usize;
+
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+            origin: min,
+        }
+    }
+
+    /// Builds the grid out of rows that must all be of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if let Some(y) = rows.iter().position(|r| r.len() != width) {
+            return Err(eyre!("Row {y} is {} cells wide, expected {width}!", rows[y].len()));
+        }
+
+        Ok(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+            origin: Pos::default(),
+        })
+    }
+
+    /// Parses a character map, one row per line.
+    pub fn parse(source: &str, f: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        Ok(Self::parse_marked(source, "", f)?.0)
+    }
+
+    /// Parses a character map, additionally remembering where each of the `marks` characters was.
+    /// The marked cells still get their value from `f`.
+    pub fn parse_marked(source: &str, marks: &str, mut f: impl FnMut(char) -> Result<T>) -> Result<(Self, Marks)> {
+        let mut found = Marks::default();
+
+        let rows = source
+            .lines()
+            .enumerate()
+            .map(|(y, l)| {
+                l.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        if marks.contains(c) {
+                            found.0.entry(c).or_default().push(Pos::from((x, y)));
+                        }
+                        f(c).map_err(|e| e.wrap_err(format!("Invalid cell at line {}, column {}", y + 1, x + 1)))
+                    })
+                    .collect::<Result<Vec<T>>>()
+            })
+            .collect::<Result<Vec<Vec<T>>>>()?;
+
+        Ok((Self::from_rows(rows)?, found))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The top-left corner.
+    pub fn min(&self) -> Pos {
+        self.origin
+    }
+
+    /// The bottom-right corner.
+    pub fn max(&self) -> Pos {
+        self.origin + Vec2::new(self.width as isize - 1, self.height as isize - 1)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        let (min, max) = (self.min(), self.max());
+        (min.x..=max.x).contains(&pos.x) && (min.y..=max.y).contains(&pos.y)
+    }
+
+    fn offset(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| {
+            let p = pos - self.origin;
+            p.y as usize * self.width + p.x as usize
+        })
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.offset(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let (min, max) = (self.min(), self.max());
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Pos::new(x, y)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| pos + d.vector())
+            .filter(|p| self.contains(*p))
+    }
+
+    /// All the neighbours of `pos` that lie inside the grid, diagonals included.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .map(move |d| pos + d.vector())
+            .filter(|p| self.contains(*p))
+    }
+
+    /// All the rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// The cells of row `y`, left to right.
+    pub fn row(&self, y: isize) -> impl Iterator<Item = &T> {
+        self.ray(Pos::new(self.origin.x - 1, y), Direction::Right.vector())
+            .map(|(_, c)| c)
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: isize) -> impl Iterator<Item = &T> {
+        self.ray(Pos::new(x, self.origin.y - 1), Direction::Down.vector())
+            .map(|(_, c)| c)
+    }
+
+    /// Walks from `start` (exclusive) in steps of `step` until leaving the grid.
+    pub fn ray(&self, start: Pos, step: Vec2<isize>) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(Some(start + step), move |&p| Some(p + step)).map_while(|p| self.get(p).map(|c| (p, c)))
+    }
+
+    /// Extends the grid so that `pos` is inside it, filling all the new cells with `fill`.
+    pub fn grow_to_include(&mut self, pos: Pos, fill: T)
+    where
+        T: Clone,
+    {
+        if self.contains(pos) {
+            return;
+        }
+
+        let (min, max) = if self.cells.is_empty() {
+            (pos, pos)
+        } else {
+            let (min, max) = (self.min(), self.max());
+            (
+                Pos::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Pos::new(max.x.max(pos.x), max.y.max(pos.y)),
+            )
+        };
+
+        let mut grown = Self::with_bounds(min, max, fill);
+        for (p, c) in self.positions().zip(self.cells.drain(..)) {
+            grown[p] = c;
+        }
+
+        *self = grown;
+    }
+
+    /// Sets the cell at `pos`, growing the grid first if needed.
+    pub fn insert(&mut self, pos: Pos, value: T, fill: T)
+    where
+        T: Clone,
+    {
+        self.grow_to_include(pos, fill);
+        self[pos] = value;
+    }
+}
+
+/// An empty grid, to be grown as needed.
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            cells: Vec::new(),
+            width: 0,
+            height: 0,
+            origin: Pos::default(),
+        }
+    }
+}
+
+impl<T> ops::Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid {:?}..={:?}!", self.min(), self.max()))
+    }
+}
+
+impl<T> ops::IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (min, max) = (self.min(), self.max());
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid {min:?}..={max:?}!"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for c in row {
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Grid")
+            .field("min", &self.min())
+            .field("max", &self.max())
+            .field("rows", &self.rows().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(c: char) -> Result<u8> {
+        c.to_digit(10).map(|d| d as u8).ok_or_else(|| eyre!("Not a digit: {c}"))
+    }
+
+    #[test]
+    fn test_parse() {
+        let (g, marks) = Grid::parse_marked("S12\n34E", "SE", |c| match c {
+            'S' => Ok(0),
+            'E' => Ok(9),
+            _ => digits(c),
+        })
+        .unwrap();
+
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(marks.get('S'), Some(Pos::new(0, 0)));
+        assert_eq!(marks.get('E'), Some(Pos::new(2, 1)));
+        assert_eq!(marks.get('X'), None);
+        assert_eq!(g[Pos::new(2, 1)], 9);
+        assert_eq!(g.to_string(), "012\n349\n");
+
+        assert!(Grid::parse("12\n3", digits).is_err());
+        assert!(Grid::parse("12\n3x", digits).is_err());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = Grid::new(3, 2, 0);
+
+        assert_eq!(
+            g.neighbors4(Pos::new(0, 0)).collect::<Vec<_>>(),
+            vec![Pos::new(1, 0), Pos::new(0, 1)]
+        );
+        assert_eq!(g.neighbors4(Pos::new(1, 1)).count(), 3);
+        assert_eq!(g.neighbors8(Pos::new(1, 0)).count(), 5);
+        assert_eq!(g.neighbors8(Pos::new(5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn test_lines() {
+        let g = Grid::parse("123\n456", digits).unwrap();
+
+        assert_eq!(g.row(1).copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(g.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(
+            g.ray(Pos::new(2, 1), Direction::Left.vector())
+                .map(|(_, c)| *c)
+                .collect::<Vec<_>>(),
+            vec![5, 4]
+        );
+        assert_eq!(g.rows().count(), 2);
+    }
+
+    #[test]
+    fn test_grow() {
+        let mut g = Grid::new(2, 2, '.');
+        g.insert(Pos::new(-2, 3), '#', ' ');
+
+        assert_eq!(g.min(), Pos::new(-2, 0));
+        assert_eq!(g.max(), Pos::new(1, 3));
+        assert_eq!(g.to_string(), "  ..\n  ..\n    \n#   \n");
+        assert_eq!(g.get(Pos::new(-3, 0)), None);
+    }
+}
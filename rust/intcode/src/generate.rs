@@ -0,0 +1,236 @@
+//! Random Intcode programs that always halt, `size` is the number of instructions.
+//!
+//! The code never writes over itself and only ever jumps forward, every instruction works on the data cells
+//! placed after the final `99`. A few of them are constants that are never written, so there is always
+//! something small to add when the values would grow out of an `i32`.
+
+use aoc_common::rand::{seq::SliceRandom, Rng};
+
+/// The parts of Intcode the program may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Opcodes 3 and 4, the program ends by outputting a cell. Like those of day 5, it reads at most one input
+    /// and every output before the last is a zero, the mark of a passed test.
+    pub io: bool,
+    /// Opcodes 5 and 6, their targets are immediate.
+    pub jumps: bool,
+    /// Opcodes 7 and 8.
+    pub compare: bool,
+    /// Immediate parameters besides the jump targets.
+    pub immediate: bool,
+    /// Negative numbers, the programs of day 2 have none.
+    pub negative: bool,
+}
+
+impl Options {
+    /// Everything up to day 5, the programs never need a relative base.
+    pub const ALL: Options = Options {
+        io: true,
+        jumps: true,
+        compare: true,
+        immediate: true,
+        negative: true,
+    };
+
+    /// Only the additions and multiplications in position mode of day 2.
+    pub const DAY_02: Options = Options {
+        io: false,
+        jumps: false,
+        compare: false,
+        immediate: false,
+        negative: false,
+    };
+}
+
+const CONSTANTS: usize = 4;
+const VARIABLES: usize = 8;
+/// Inputs are assumed to be within `-INPUT..=INPUT`.
+const INPUT: i64 = 100;
+const LIMIT: i64 = 1 << 30;
+
+#[derive(Debug, Clone, Copy)]
+enum Param {
+    Cell(usize),
+    Immediate(i32),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    /// Opcodes 1, 2, 7 and 8.
+    Compute(u32, Param, Param, usize),
+    Input(usize),
+    Output(Param),
+    /// Opcodes 5 and 6, to the instruction with the given index.
+    Jump(u32, Param, usize),
+}
+
+impl Instruction {
+    fn len(&self) -> usize {
+        match self {
+            Instruction::Compute(..) => 4,
+            Instruction::Input(_) | Instruction::Output(_) => 2,
+            Instruction::Jump(..) => 3,
+        }
+    }
+}
+
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    program(rng, size, Options::ALL)
+}
+
+pub fn program(rng: &mut impl Rng, size: usize, options: Options) -> String {
+    let constants: Vec<i32> = (0..CONSTANTS).map(|_| rng.gen_range(0..=10)).collect();
+    let least = if options.negative { -100 } else { 0 };
+    let variables: Vec<i32> = (0..VARIABLES).map(|_| rng.gen_range(least..=100)).collect();
+
+    // the largest absolute value each cell can hold, whichever jumps are taken
+    let mut bounds: Vec<i64> = constants
+        .iter()
+        .chain(&variables)
+        .map(|&v| i64::from(v).abs())
+        .collect();
+    let mut code = vec![];
+    let mut read = false;
+
+    for i in 0..size {
+        let mut kinds = vec![1, 2];
+        if options.compare {
+            kinds.extend([7, 8]);
+        }
+        if options.io {
+            kinds.push(4);
+        }
+        if options.io && !read {
+            kinds.push(3);
+        }
+        if options.jumps && i + 1 < size {
+            kinds.extend([5, 6]);
+        }
+
+        let instruction = match *kinds.choose(rng).unwrap() {
+            3 => {
+                read = true;
+                let target = CONSTANTS + rng.gen_range(0..VARIABLES);
+                bounds[target] = bounds[target].max(INPUT);
+                Instruction::Input(target)
+            }
+            4 => Instruction::Output(Param::Immediate(0)),
+            jump @ (5 | 6) => Instruction::Jump(jump, param(rng, options), rng.gen_range(i + 1..=size)),
+            op => {
+                let (a, b) = (param(rng, options), param(rng, options));
+                let target = CONSTANTS + rng.gen_range(0..VARIABLES);
+                let bound = |p| match p {
+                    Param::Cell(c) => bounds[c],
+                    Param::Immediate(v) => i64::from(v).abs(),
+                };
+
+                let (op, a, b, res) = match op {
+                    1 if bound(a) + bound(b) <= LIMIT => (1, a, b, bound(a) + bound(b)),
+                    2 if bound(a) * bound(b) <= LIMIT => (2, a, b, bound(a) * bound(b)),
+                    7 | 8 => (op, a, b, 1),
+                    // adding two constants always fits
+                    _ => {
+                        let (a, b) = (
+                            Param::Cell(rng.gen_range(0..CONSTANTS)),
+                            Param::Cell(rng.gen_range(0..CONSTANTS)),
+                        );
+                        (1, a, b, bound(a) + bound(b))
+                    }
+                };
+
+                bounds[target] = bounds[target].max(res);
+                Instruction::Compute(op, a, b, target)
+            }
+        };
+
+        code.push(instruction);
+    }
+
+    if options.io {
+        code.push(Instruction::Output(Param::Cell(
+            CONSTANTS + rng.gen_range(0..VARIABLES),
+        )));
+    }
+
+    // the jumps may target the final `99` too
+    let addresses: Vec<usize> = code
+        .iter()
+        .scan(0, |address, ins| {
+            let a = *address;
+            *address += ins.len();
+            Some(a)
+        })
+        .collect();
+    let halt = code.iter().map(Instruction::len).sum::<usize>();
+    let data = halt + 1;
+
+    let param = |p: Param| match p {
+        Param::Cell(c) => (0, (data + c) as i32),
+        Param::Immediate(v) => (1, v),
+    };
+
+    let mut memory = vec![];
+    for ins in &code {
+        match *ins {
+            Instruction::Compute(op, a, b, target) => {
+                let ((ma, a), (mb, b)) = (param(a), param(b));
+                memory.extend([op as i32 + 100 * ma + 1000 * mb, a, b, (data + target) as i32]);
+            }
+            Instruction::Input(target) => memory.extend([3, (data + target) as i32]),
+            Instruction::Output(p) => {
+                let (m, p) = param(p);
+                memory.extend([4 + 100 * m, p]);
+            }
+            Instruction::Jump(op, cond, to) => {
+                let (m, cond) = param(cond);
+                let to = addresses.get(to).copied().unwrap_or(halt);
+                memory.extend([op as i32 + 100 * m + 1000, cond, to as i32]);
+            }
+        }
+    }
+
+    memory.push(99);
+    memory.extend(constants);
+    memory.extend(variables);
+
+    memory.iter().map(i32::to_string).collect::<Vec<_>>().join(",")
+}
+
+fn param(rng: &mut impl Rng, options: Options) -> Param {
+    if options.immediate && rng.gen_bool(0.3) {
+        let least = if options.negative { -100 } else { 0 };
+        Param::Immediate(rng.gen_range(least..=100))
+    } else {
+        Param::Cell(rng.gen_range(0..CONSTANTS + VARIABLES))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Executor;
+    use aoc_common::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..50 {
+            let source = input(&mut StdRng::seed_from_u64(seed), 100);
+            let mut e = Executor::with_input(&source, "5").unwrap();
+            e.execute().unwrap();
+
+            let (_, tests) = e.output.split_last().unwrap();
+            assert!(tests.iter().all(|&t| t == 0));
+        }
+    }
+
+    #[test]
+    fn test_day_02() {
+        let source = program(&mut StdRng::seed_from_u64(1), 20, Options::DAY_02);
+        let memory: Vec<i32> = source.split(',').map(|v| v.parse().unwrap()).collect();
+
+        // twenty additions or multiplications, then the halt
+        assert!(memory[..80].chunks(4).all(|c| c[0] == 1 || c[0] == 2));
+        assert_eq!(memory[80], 99);
+        Executor::new(&source).unwrap().execute().unwrap();
+    }
+}
@@ -0,0 +1,120 @@
+use super::{Context, Executor};
+use crate::get2;
+use aoc_common::{eyre::eyre, tracing::debug, Result};
+
+pub type Data = Vec<i64>;
+
+pub fn load_instructions(executor: &mut Executor) {
+    executor.add_instruction(1, ins_add);
+    executor.add_instruction(2, ins_multiply);
+    executor.add_instruction(3, ins_input);
+    executor.add_instruction(4, ins_output);
+    executor.add_instruction(5, ins_jump_true);
+    executor.add_instruction(6, ins_jump_false);
+    executor.add_instruction(7, ins_less_than);
+    executor.add_instruction(8, ins_equals);
+    executor.add_instruction(9, ins_adjust_base);
+}
+
+fn ins_add(ctx: &mut Context) -> Result<usize> {
+    let (v1, v2) = get2!(ctx);
+    let target = ctx.get_address(3)?;
+
+    let v = v1
+        .checked_add(v2)
+        .ok_or_else(|| eyre!("{v1} + {v2} does not fit into an i64!"))?;
+    ctx.memory.set(target, v)?;
+
+    Ok(ctx.pointer + 4)
+}
+
+fn ins_multiply(ctx: &mut Context) -> Result<usize> {
+    let (v1, v2) = get2!(ctx);
+    let target = ctx.get_address(3)?;
+
+    let v = v1
+        .checked_mul(v2)
+        .ok_or_else(|| eyre!("{v1} * {v2} does not fit into an i64!"))?;
+    ctx.memory.set(target, v)?;
+
+    Ok(ctx.pointer + 4)
+}
+
+fn ins_input(ctx: &mut Context) -> Result<usize> {
+    let v = ctx.take_input().ok_or_else(|| eyre!("There is no input left!"))?;
+    debug!(input = v, "loaded input");
+
+    let target = ctx.get_address(1)?;
+
+    ctx.memory.set(target, v)?;
+
+    Ok(ctx.pointer + 2)
+}
+
+fn ins_output(ctx: &mut Context) -> Result<usize> {
+    let v = ctx.get_param(1)?;
+    debug!(output = v, "output");
+    ctx.set_output(v);
+
+    Ok(ctx.pointer + 2)
+}
+
+fn ins_jump_true(ctx: &mut Context) -> Result<usize> {
+    let v1 = ctx.get_param(1)?;
+
+    if v1 != 0 {
+        let v2 = ctx.get_param(2)?;
+        return ctx.memory.address(v2);
+    }
+
+    Ok(ctx.pointer + 3)
+}
+
+fn ins_jump_false(ctx: &mut Context) -> Result<usize> {
+    let v1 = ctx.get_param(1)?;
+
+    if v1 == 0 {
+        let v2 = ctx.get_param(2)?;
+        return ctx.memory.address(v2);
+    }
+
+    Ok(ctx.pointer + 3)
+}
+
+fn ins_less_than(ctx: &mut Context) -> Result<usize> {
+    let (v1, v2) = get2!(ctx);
+    let target = ctx.get_address(3)?;
+
+    ctx.memory.set(target, i64::from(v1 < v2))?;
+
+    Ok(ctx.pointer + 4)
+}
+
+fn ins_equals(ctx: &mut Context) -> Result<usize> {
+    let (v1, v2) = get2!(ctx);
+    let target = ctx.get_address(3)?;
+
+    ctx.memory.set(target, i64::from(v1 == v2))?;
+
+    Ok(ctx.pointer + 4)
+}
+
+fn ins_adjust_base(ctx: &mut Context) -> Result<usize> {
+    let v = ctx.get_param(1)?;
+    let base = *ctx.relative_base;
+    *ctx.relative_base = base
+        .checked_add(v)
+        .ok_or_else(|| eyre!("{base} + {v} does not fit into an i64!"))?;
+    debug!(base = *ctx.relative_base, "adjusted relative base");
+
+    Ok(ctx.pointer + 2)
+}
+
+#[macro_export]
+macro_rules! get2 {
+    ( $c:expr ) => {{
+        let a = $c.get_param(1)?;
+        let b = $c.get_param(2)?;
+        (a, b)
+    }};
+}
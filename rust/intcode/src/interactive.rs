@@ -0,0 +1,51 @@
+//! Playing a program by hand: the inputs are typed in as it asks for them, the outputs are printed as they come.
+
+use crate::{Executor, State};
+use aoc_common::{eyre::eyre, Result};
+use std::io::{self, BufRead, Write};
+
+/// Runs the program in the terminal until it halts.
+pub fn stdin(executor: &mut Executor) -> Result<()> {
+    run(executor, io::stdin().lock(), io::stdout())
+}
+
+/// Runs the program until it halts, reading an input from a line of `input` whenever it needs one.
+pub fn run(executor: &mut Executor, mut input: impl BufRead, mut output: impl Write) -> Result<()> {
+    loop {
+        match executor.run()? {
+            State::Halted => return Ok(()),
+            State::Output(v) => writeln!(output, "{v}")?,
+            State::NeedsInput => {
+                write!(output, "Input instruction: ")?;
+                output.flush()?;
+
+                let mut line = String::new();
+                if input.read_line(&mut line)? == 0 {
+                    return Err(eyre!("The input ended while the program was waiting for more!"));
+                }
+                executor.push_input(line.trim().parse()?);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        // adds up two numbers
+        let mut e = Executor::new("3,11,3,12,1,11,12,11,4,11,99,0,0").unwrap();
+        let mut output = vec![];
+        run(&mut e, "2\n40\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Input instruction: Input instruction: 42\n"
+        );
+
+        let mut e = Executor::new("3,0,99").unwrap();
+        let e = run(&mut e, "".as_bytes(), vec![]).unwrap_err();
+        assert_eq!(e.to_string(), "The input ended while the program was waiting for more!");
+    }
+}
@@ -0,0 +1,412 @@
+//! The Intcode computer of 2019, for every day that runs an Intcode program.
+//!
+//! It started out as the executor of day 5, and runs the programs of day 2 too.
+//!
+//! The values are `i64`, an instruction whose result does not fit fails instead of wrapping around.
+//! The memory grows as the program writes past its end, up to a limit, see [`Memory::with_limit`].
+//!
+//! [`Executor::run`] stops at every output and whenever the program waits for an input, so that the caller can
+//! feed it more with [`Executor::push_input`]. [`interactive`] plays the program in a terminal instead,
+//! and a [`network::Network`] connects many of them.
+
+use aoc_common::{
+    eyre::{eyre, Context as _},
+    tracing::debug,
+    Result,
+};
+use std::collections::{HashMap, VecDeque};
+
+pub mod generate;
+mod instructions;
+pub mod interactive;
+pub mod network;
+pub use instructions::Data;
+use instructions::*;
+mod memory;
+pub use memory::*;
+
+#[allow(unused, dead_code)]
+pub type Opcode = u32;
+pub type Instruction = fn(&mut Context) -> Result<usize>;
+pub type Lookup = HashMap<Opcode, Box<Instruction>>;
+
+pub struct Context<'a> {
+    memory: &'a mut Memory,
+    input: &'a mut VecDeque<i64>,
+    output: &'a mut Data,
+    pointer: usize,
+    relative_base: &'a mut i64,
+}
+
+impl Context<'_> {
+    fn get_param(&self, offset: usize) -> Result<i64> {
+        self.memory.get_param(self.pointer, offset, *self.relative_base)
+    }
+
+    /// Where the parameter at `offset` writes to.
+    fn get_address(&self, offset: usize) -> Result<usize> {
+        self.memory.get_address(self.pointer, offset, *self.relative_base)
+    }
+
+    fn take_input(&mut self) -> Option<i64> {
+        self.input.pop_front()
+    }
+
+    fn set_output(&mut self, o: i64) {
+        self.output.push(o);
+    }
+}
+
+/// Where [`Executor::run`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Halted,
+    /// The next instruction reads an input and there is none left, it runs once one is pushed.
+    NeedsInput,
+    Output(i64),
+}
+
+pub struct Executor {
+    memory: Memory,
+    current_instruction: usize,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    pub output: Data,
+    instructions: Lookup,
+}
+
+impl Executor {
+    pub fn new(input: &str) -> Result<Self> {
+        let memory = Memory::from(input)?;
+
+        Ok(Self::from(memory, vec![]))
+    }
+
+    pub fn with_input(source: &str, input: &str) -> Result<Self> {
+        let memory = Memory::from(source)?;
+        let input = parse_input(input)?;
+
+        Ok(Self::from(memory, input))
+    }
+
+    pub fn from(memory: Memory, input: Data) -> Self {
+        let mut e = Executor {
+            memory,
+            current_instruction: 0,
+            relative_base: 0,
+            input: input.into(),
+            output: Vec::default(),
+            instructions: HashMap::default(),
+        };
+
+        load_instructions(&mut e);
+
+        e
+    }
+
+    pub fn add_instruction(&mut self, opcode: Opcode, ex: Instruction) {
+        self.instructions.entry(opcode).or_insert_with(|| Box::new(ex));
+    }
+
+    /// Runs the program until it halts, or until an instruction fails.
+    /// Every input it needs must have been given, the outputs are collected in `output`.
+    pub fn execute(&mut self) -> Result<()> {
+        debug!(memory = self.memory.len(), "beginning execution");
+        loop {
+            match self.run()? {
+                State::Halted => return Ok(()),
+                State::NeedsInput => {
+                    return Err(eyre!(
+                        "The program needs more input than it was given, at {}!",
+                        self.current_instruction
+                    ))
+                }
+                State::Output(_) => {}
+            }
+        }
+    }
+
+    /// Runs the program until it outputs a value, needs an input it does not have, or halts.
+    /// The output is also added to `output`.
+    pub fn run(&mut self) -> Result<State> {
+        loop {
+            // a broken instruction is left for `step` to report
+            if matches!(self.memory.get_opcode(self.current_instruction), Ok(3)) && self.input.is_empty() {
+                return Ok(State::NeedsInput);
+            }
+
+            let outputs = self.output.len();
+            if !self.step()? {
+                return Ok(State::Halted);
+            }
+            if let Some(&v) = self.output.get(outputs) {
+                return Ok(State::Output(v));
+            }
+        }
+    }
+
+    /// Queues an input after those not read yet.
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Runs a single instruction, `false` once the program has halted.
+    pub fn step(&mut self) -> Result<bool> {
+        let pointer = self.current_instruction;
+        match self
+            .process()
+            .wrap_err_with(|| format!("The instruction at {pointer} failed"))?
+        {
+            Some(pointer) => {
+                self.current_instruction = pointer;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    /// For setting up the program before it runs, like the noun and the verb of day 2.
+    pub fn memory_mut(&mut self) -> &mut Memory {
+        &mut self.memory
+    }
+
+    pub fn pointer(&self) -> usize {
+        self.current_instruction
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    fn process(&mut self) -> Result<Option<usize>> {
+        let pointer = self.current_instruction;
+        let opcode = self.memory.get_opcode(pointer)?;
+
+        if opcode == 99 {
+            // check early to avoid complicated match later
+            return Ok(None);
+        }
+
+        let instruction = self
+            .instructions
+            .get(&opcode)
+            .ok_or_else(|| eyre!("There is no opcode {}!", self.memory.get(pointer)))?;
+
+        let mut context = Context {
+            memory: &mut self.memory,
+            pointer,
+            input: &mut self.input,
+            output: &mut self.output,
+            relative_base: &mut self.relative_base,
+        };
+
+        let pointer = (instruction)(&mut context)?;
+
+        Ok(Some(pointer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_executor_runner(data: &str, result: &str) {
+        let mut e = Executor::new(data).unwrap();
+
+        e.execute().unwrap();
+
+        assert_eq!(e.memory.peek(), &memory::parse_input(result).unwrap());
+    }
+
+    #[test]
+    fn test_executor_simple() {
+        test_executor_runner("1002,4,3,4,33", "1002,4,3,4,99");
+        test_executor_runner("1101,100,-1,4,0", "1101,100,-1,4,99");
+
+        test_executor_runner("10001,0,0,0,99", "20002,0,0,0,99");
+        test_executor_runner("10002,3,3,3,99", "10002,3,3,9,99");
+        test_executor_runner("10002,4,4,5,99,0", "10002,4,4,5,99,9801");
+    }
+
+    /// Only additions and multiplications, from day 2.
+    #[test]
+    fn test_executor_positional() {
+        test_executor_runner("1,0,0,0,99", "2,0,0,0,99");
+        test_executor_runner("2,3,0,3,99", "2,3,0,6,99");
+        test_executor_runner("2,4,4,5,99,0", "2,4,4,5,99,9801");
+        test_executor_runner("1,1,1,4,99,5,6,0,99", "30,1,1,4,2,5,6,0,99");
+        test_executor_runner("1,9,10,3,2,3,11,0,99,30,40,50", "3500,9,10,70,2,3,11,0,99,30,40,50");
+    }
+
+    #[test]
+    fn test_memory_mut() {
+        let mut e = Executor::new("1,0,0,0,99").unwrap();
+        e.memory_mut().set(1, 4).unwrap();
+        e.memory_mut().set(2, 4).unwrap();
+        e.execute().unwrap();
+
+        assert_eq!(e.memory().peek()[0], 198);
+        assert_eq!(e.pointer(), 4);
+    }
+
+    #[test]
+    fn test_executor_longer() {
+        test_executor_runner("10002,3,3,3,10001,1,1,7,99", "10002,3,3,9,10001,1,1,6,99");
+    }
+
+    fn test_ex_input(source: &str, input: &str, result: Vec<i64>) {
+        let mut e = Executor::with_input(source, input).unwrap();
+
+        e.execute().unwrap();
+
+        assert_eq!(e.output, result);
+    }
+
+    #[test]
+    fn test_io_short() {
+        test_ex_input("3,0,4,0,99", "5", vec![5]);
+
+        test_ex_input("3,9,8,9,10,9,4,9,99,-1,8", "8", vec![1]);
+        test_ex_input("3,9,8,9,10,9,4,9,99,-1,8", "7", vec![0]);
+        test_ex_input("3,3,1108,-1,8,3,4,3,99", "8", vec![1]);
+        test_ex_input("3,9,8,9,10,9,4,9,99,-1,8", "7", vec![0]);
+    }
+
+    #[test]
+    fn test_io() {
+        let i = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+        test_ex_input(i, "5", vec![999]);
+        test_ex_input(i, "11", vec![1001]);
+        test_ex_input(i, "8", vec![1000]);
+    }
+
+    #[test]
+    fn test_input_queue() {
+        test_ex_input("3,0,3,1,4,0,4,1,99", "1,2", vec![1, 2]);
+
+        let e = Executor::with_input("3,0,3,1,99", "1").unwrap().execute().unwrap_err();
+        assert_eq!(e.to_string(), "The program needs more input than it was given, at 2!");
+    }
+
+    #[test]
+    fn test_run() {
+        let mut e = Executor::new("3,11,4,11,1001,11,1,11,4,11,99,0").unwrap();
+        assert_eq!(e.run().unwrap(), State::NeedsInput);
+        assert_eq!(e.run().unwrap(), State::NeedsInput);
+        assert_eq!(e.pointer(), 0);
+
+        e.push_input(5);
+        assert_eq!(e.run().unwrap(), State::Output(5));
+        assert_eq!(e.run().unwrap(), State::Output(6));
+        assert_eq!(e.run().unwrap(), State::Halted);
+        assert_eq!(e.run().unwrap(), State::Halted);
+        assert_eq!(e.output, vec![5, 6]);
+    }
+
+    /// The examples of the BOOST program of day 9.
+    #[test]
+    fn test_boost() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        // the quine counts in cells past its end
+        let mut e = Executor::new(quine).unwrap();
+        e.execute().unwrap();
+
+        assert_eq!(e.output, memory::parse_input(quine).unwrap());
+        assert_eq!(e.relative_base(), 16);
+        assert_eq!(e.memory().len(), 102);
+
+        let mut e = Executor::new("1102,34915192,34915192,7,4,7,99,0").unwrap();
+        e.execute().unwrap();
+        assert_eq!(e.output[0].to_string().len(), 16);
+
+        let mut e = Executor::new("104,1125899906842624,99").unwrap();
+        e.execute().unwrap();
+        assert_eq!(e.output, vec![1125899906842624]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source: &str, limit: usize| {
+            let mut e = Executor::from(Memory::from(source).unwrap().with_limit(limit), vec![]);
+            format!("{:#}", e.execute().unwrap_err())
+        };
+
+        assert_eq!(
+            error("1101,1,1,100,99", 100),
+            "The instruction at 0 failed: Address 100 is past the memory limit of 100 cells!"
+        );
+        assert_eq!(
+            error("204,-1,99", 100),
+            "The instruction at 0 failed: Address -1 is negative!"
+        );
+        assert_eq!(
+            error("1105,1,-4", 100),
+            "The instruction at 0 failed: Address -4 is negative!"
+        );
+        assert_eq!(
+            error("1,0,0,0,42", 100),
+            "The instruction at 4 failed: There is no opcode 42!"
+        );
+        assert_eq!(
+            error("301,0,0,0,99", 100),
+            "The instruction at 0 failed: In the instruction 301: Invalid parameter mode 3!"
+        );
+        assert_eq!(
+            error("-9999,0,0,0,99", 100),
+            "The instruction at 0 failed: The instruction -9999 is negative!"
+        );
+        assert_eq!(
+            error("-1,0,0,0", 100),
+            "The instruction at 0 failed: The instruction -1 is negative!"
+        );
+        // running off the end reads a zero
+        assert_eq!(
+            error("1101,1,1,5", 100),
+            "The instruction at 4 failed: There is no opcode 0!"
+        );
+        assert_eq!(
+            error("1102,4611686018427387904,2,0,99", 100),
+            "The instruction at 0 failed: 4611686018427387904 * 2 does not fit into an i64!"
+        );
+        assert_eq!(
+            error("109,9223372036854775807,109,1,99", 100),
+            "The instruction at 2 failed: 9223372036854775807 + 1 does not fit into an i64!"
+        );
+        assert_eq!(
+            error("109,9223372036854775807,204,1,99", 100),
+            "The instruction at 2 failed: 9223372036854775807 + 1 does not fit into an i64!"
+        );
+        assert_eq!(
+            error("109,9223372036854775807,21101,1,1,1,99", 100),
+            "The instruction at 2 failed: 9223372036854775807 + 1 does not fit into an i64!"
+        );
+    }
+
+    #[test]
+    fn test_relative_write() {
+        test_ex_input("109,7,203,1,204,1,99,0,0", "42", vec![42]);
+
+        let mut e = Executor::new("109,8,21101,3,4,1,204,1,99,0").unwrap();
+        e.execute().unwrap();
+        assert_eq!(e.output, vec![7]);
+
+        // the base moved by a relative parameter
+        let mut e = Executor::with_input("109,6,209,1,203,5,99,-3,0", "5").unwrap();
+        e.execute().unwrap();
+        assert_eq!(e.relative_base(), 3);
+        assert_eq!(e.memory().peek()[8], 5);
+    }
+
+    #[test]
+    fn test_io_long() {
+        test_ex_input("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9", "0", vec![0]);
+        test_ex_input("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9", "1", vec![1]);
+
+        test_ex_input("3,3,1105,-1,9,1101,0,0,12,4,12,99,1", "0", vec![0]);
+        test_ex_input("3,3,1105,-1,9,1101,0,0,12,4,12,99,1", "1", vec![1]);
+    }
+}
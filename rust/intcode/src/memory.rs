@@ -0,0 +1,259 @@
+use super::{Data, Opcode};
+use aoc_common::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+
+/// How many cells a program may use unless told otherwise, 64 MiB worth of them.
+pub const DEFAULT_LIMIT: usize = 1 << 23;
+
+#[derive(PartialEq, PartialOrd, Ord, Eq, Debug, Copy, Clone)]
+pub enum ParameterMode {
+    Position,
+    Immediate,
+    /// Relative to the relative base of the executor.
+    Relative,
+}
+
+impl ParameterMode {
+    fn from_digit(value: i64) -> Result<Self> {
+        use ParameterMode::*;
+
+        match value {
+            0 => Ok(Position),
+            1 => Ok(Immediate),
+            2 => Ok(Relative),
+            _ => Err(eyre!("Invalid parameter mode {value}!")),
+        }
+    }
+}
+
+/// The cells of a program, every cell past its end reads zero until it is written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Memory {
+    data: Data,
+    limit: usize,
+}
+
+impl Memory {
+    pub fn from(data: &str) -> Result<Self> {
+        Ok(Memory {
+            data: parse_input(data)?,
+            limit: DEFAULT_LIMIT,
+        })
+    }
+
+    /// The program may use at most `limit` cells, any address past them is an error.
+    pub fn with_limit(self, limit: usize) -> Self {
+        Self { limit, ..self }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The cell at `value`, as long as it is a valid address.
+    pub fn address(&self, value: i64) -> Result<usize> {
+        match usize::try_from(value) {
+            Ok(address) if address < self.limit => Ok(address),
+            Ok(_) => Err(eyre!(
+                "Address {value} is past the memory limit of {} cells!",
+                self.limit
+            )),
+            Err(_) => Err(eyre!("Address {value} is negative!")),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> i64 {
+        self.data.get(index).copied().unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The instruction at `index`, whose digits would make no sense if it were negative.
+    fn instruction(&self, index: usize) -> Result<i64> {
+        match self.get(index) {
+            instruction if instruction < 0 => Err(eyre!("The instruction {instruction} is negative!")),
+            instruction => Ok(instruction),
+        }
+    }
+
+    pub fn get_opcode(&self, index: usize) -> Result<Opcode> {
+        Ok((self.instruction(index)? % 100) as Opcode)
+    }
+
+    /// The mode of the parameter at `offset`, from the digits of the instruction above its opcode.
+    pub fn get_param_mode(&self, index: usize, offset: usize) -> Result<ParameterMode> {
+        let instruction = self.instruction(index)?;
+
+        let digit = instruction / 100 / 10i64.pow(offset.saturating_sub(1) as u32) % 10;
+        ParameterMode::from_digit(digit).wrap_err_with(|| format!("In the instruction {instruction}"))
+    }
+
+    pub fn get_param(&self, index: usize, offset: usize, base: i64) -> Result<i64> {
+        use ParameterMode::*;
+
+        let param_mode = self.get_param_mode(index, offset)?;
+        let value = self.get_value(index, offset);
+
+        Ok(match param_mode {
+            Position => self.get(self.address(value)?),
+            Immediate => value,
+            Relative => self.get(self.relative(base, value)?),
+        })
+    }
+
+    /// The address a parameter is written to, moved by `base` in relative mode.
+    /// Writes are never immediate, a parameter in immediate mode is taken as a position like it always was.
+    pub fn get_address(&self, index: usize, offset: usize, base: i64) -> Result<usize> {
+        let value = self.get_value(index, offset);
+
+        match self.get_param_mode(index, offset)? {
+            ParameterMode::Relative => self.relative(base, value),
+            _ => self.address(value),
+        }
+    }
+
+    fn relative(&self, base: i64, value: i64) -> Result<usize> {
+        let address = base
+            .checked_add(value)
+            .ok_or_else(|| eyre!("{base} + {value} does not fit into an i64!"))?;
+        self.address(address)
+    }
+
+    pub fn get_value(&self, index: usize, offset: usize) -> i64 {
+        self.get(index + offset)
+    }
+
+    /// Grows the memory up to `index` if needed.
+    pub fn set(&mut self, index: usize, data: i64) -> Result<()> {
+        if index >= self.limit {
+            return Err(eyre!(
+                "Address {index} is past the memory limit of {} cells!",
+                self.limit
+            ));
+        }
+        if index >= self.data.len() {
+            self.data.resize(index + 1, 0);
+        }
+
+        self.data[index] = data;
+        Ok(())
+    }
+
+    pub fn peek(&self) -> &Data {
+        &self.data
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Data> {
+    input
+        .trim()
+        .split(',')
+        .map(|c| {
+            c.trim()
+                .parse::<i64>()
+                .wrap_err_with(|| format!("{c:?} is not an Intcode value"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_param_mode(opcode in 1..100u32, modes in prop::array::uniform3(0..3u32)) {
+            let instruction = opcode + modes.iter().enumerate().map(|(i, &m)| m * 10u32.pow(i as u32 + 2)).sum::<u32>();
+            let m = Memory::from(&format!("{instruction},0,0,0")).unwrap();
+
+            prop_assert_eq!(m.get_opcode(0).unwrap(), opcode);
+            for (offset, &mode) in modes.iter().enumerate() {
+                let expected = [ParameterMode::Position, ParameterMode::Immediate, ParameterMode::Relative][mode as usize];
+                prop_assert_eq!(m.get_param_mode(0, offset + 1).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn prop_input_roundtrip(data in prop::collection::vec(any::<i64>(), 1..50)) {
+            let source = data.iter().map(i64::to_string).collect::<Vec<_>>().join(",");
+            prop_assert_eq!(parse_input(&source).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_memory_loading() {
+        let m = Memory::from("10001,0,0,0,99").unwrap();
+
+        let i = m.get_opcode(0).unwrap();
+        assert_eq!(i, 1);
+    }
+
+    #[test]
+    fn test_input_parser() {
+        let r = parse_input("1,2,3,4").unwrap();
+        assert_eq!(r, vec![1, 2, 3, 4]);
+        let r = parse_input("1,-2,3,-4").unwrap();
+        assert_eq!(r, vec![1, -2, 3, -4]);
+
+        let e = parse_input("").unwrap_err();
+        assert_eq!(e.to_string(), "\"\" is not an Intcode value");
+        assert!(Memory::from("1,2,x").is_err());
+    }
+
+    #[test]
+    fn test_memory_param() {
+        let a = Memory::from("1002,4,3,4,33").unwrap();
+        assert_eq!(a.get_opcode(0).unwrap(), 2);
+        assert_eq!(a.get_param(0, 1, 0).unwrap(), 33);
+        assert_eq!(a.get_param(0, 2, 0).unwrap(), 3);
+        assert_eq!(a.get_value(0, 3), 4);
+        assert_eq!(a.get_address(0, 3, 0).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_memory_relative() {
+        let a = Memory::from("22201,1,2,-2,7,9").unwrap();
+        assert_eq!(a.get_param_mode(0, 3).unwrap(), ParameterMode::Relative);
+        assert_eq!(a.get_param(0, 1, 3).unwrap(), 7);
+        assert_eq!(a.get_param(0, 2, 3).unwrap(), 9);
+        assert_eq!(a.get_address(0, 3, 3).unwrap(), 1);
+        // immediate writes stay positions
+        assert_eq!(Memory::from("11101,1,2,3").unwrap().get_address(0, 3, 3).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_memory_growing() {
+        let mut m = Memory::from("1,2,3").unwrap().with_limit(10);
+        assert_eq!(m.get(1000), 0);
+
+        m.set(6, 4).unwrap();
+        assert_eq!(m.peek(), &vec![1, 2, 3, 0, 0, 0, 4]);
+
+        let e = m.set(10, 1).unwrap_err();
+        assert_eq!(e.to_string(), "Address 10 is past the memory limit of 10 cells!");
+        assert_eq!(m.address(-1).unwrap_err().to_string(), "Address -1 is negative!");
+        assert_eq!(m.len(), 7);
+    }
+
+    #[test]
+    fn test_opcode_parser() {
+        let a = Memory::from("2").unwrap();
+        assert_eq!(a.get_opcode(0).unwrap(), 2);
+        let a = Memory::from("20").unwrap();
+        assert_eq!(a.get_opcode(0).unwrap(), 20);
+        let a = Memory::from("1020").unwrap();
+        assert_eq!(a.get_opcode(0).unwrap(), 20);
+        let a = Memory::from("1122").unwrap();
+        assert_eq!(a.get_opcode(0).unwrap(), 22);
+        assert!(Memory::from("-1").unwrap().get_opcode(0).is_err());
+    }
+}
@@ -0,0 +1,446 @@
+//! Many Intcode machines talking to each other, like the amplifiers of day 7 or the packet network of day 23.
+//!
+//! Every machine reads from one named channel and writes either to another one, or in packets whose first value
+//! names the channel the rest goes to. The network runs the machines in rounds until none of them has anything
+//! left to do, then asks its [`Hook`]s whether to go on.
+
+use crate::{Executor, State};
+use aoc_common::{eyre::eyre, tracing::debug, Result};
+use std::{
+    collections::{HashMap, VecDeque},
+    mem,
+    sync::mpsc,
+    thread,
+};
+
+/// Named queues of values, created on first use.
+#[derive(Debug, Default)]
+pub struct Channels(HashMap<String, VecDeque<i64>>);
+
+impl Channels {
+    pub fn send(&mut self, name: &str, values: impl IntoIterator<Item = i64>) {
+        self.0.entry(name.to_string()).or_default().extend(values);
+    }
+
+    /// Everything waiting in the channel, oldest first.
+    pub fn take(&mut self, name: &str) -> Vec<i64> {
+        self.0.get_mut(name).map(|c| c.drain(..).collect()).unwrap_or_default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&VecDeque<i64>> {
+        self.0.get(name)
+    }
+
+    /// The latest value sent to the channel that is still waiting.
+    pub fn last(&self, name: &str) -> Option<i64> {
+        self.get(name)?.back().copied()
+    }
+
+    /// Sends everything waiting in `other` on to the same channels here.
+    fn append(&mut self, other: Channels) {
+        for (name, values) in other.0 {
+            self.send(&name, values);
+        }
+    }
+
+    fn is_empty(&self, name: &str) -> bool {
+        self.get(name).is_none_or(VecDeque::is_empty)
+    }
+}
+
+/// Where the outputs of a machine go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    Channel(String),
+    /// Packets of the given size, the first value is the name of the channel the others are sent to.
+    Packets(usize),
+}
+
+pub struct Machine {
+    executor: Executor,
+    input: String,
+    output: Route,
+    /// Read instead of waiting when the channel is empty.
+    idle: Option<i64>,
+    /// The start of a packet that is not complete yet.
+    pending: Vec<i64>,
+    halted: bool,
+}
+
+impl Machine {
+    /// A machine that reads from `input` and writes to `output`.
+    pub fn new(executor: Executor, input: &str, output: &str) -> Self {
+        Self {
+            executor,
+            input: input.to_string(),
+            output: Route::Channel(output.to_string()),
+            idle: None,
+            pending: vec![],
+            halted: false,
+        }
+    }
+
+    pub fn with_output(self, output: Route) -> Self {
+        Self { output, ..self }
+    }
+
+    /// The machine reads `value` when its channel is empty, instead of waiting.
+    pub fn with_idle_input(self, value: i64) -> Self {
+        Self {
+            idle: Some(value),
+            ..self
+        }
+    }
+
+    pub fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    /// Runs the machine on `inbox` until it waits for more, or reads its idle input for the second time.
+    /// The outputs are taken from the executor.
+    fn turn(&mut self, inbox: Vec<i64>) -> Result<Vec<i64>> {
+        if self.halted {
+            return Ok(vec![]);
+        }
+
+        inbox.into_iter().for_each(|v| self.executor.push_input(v));
+        let mut idled = false;
+        loop {
+            match self.executor.run()? {
+                State::Output(_) => {}
+                State::Halted => {
+                    self.halted = true;
+                    break;
+                }
+                State::NeedsInput => match self.idle {
+                    Some(v) if !idled => {
+                        idled = true;
+                        self.executor.push_input(v);
+                    }
+                    _ => break,
+                },
+            }
+        }
+
+        Ok(mem::take(&mut self.executor.output))
+    }
+
+    fn route(&mut self, outputs: Vec<i64>, channels: &mut Channels) -> Result<()> {
+        match &self.output {
+            Route::Channel(name) => channels.send(name, outputs),
+            Route::Packets(size) if *size < 2 => {
+                return Err(eyre!(
+                    "A packet of {size} values cannot hold both an address and a value!"
+                ))
+            }
+            Route::Packets(size) => {
+                self.pending.extend(outputs);
+                while self.pending.len() >= *size {
+                    let packet: Vec<i64> = self.pending.drain(..size).collect();
+                    debug!(?packet, "routed a packet");
+                    channels.send(&packet[0].to_string(), packet[1..].iter().copied());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// One machine after the other, each sees what those before it sent in the same round.
+    RoundRobin,
+    /// Every machine on a thread of its own for the whole run, what they send is delivered once the round is over.
+    Threads,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Stop,
+}
+
+/// Called whenever the network has gone quiet, to wake it up again or to end the run.
+pub trait Hook {
+    fn idle(&mut self, channels: &mut Channels) -> Result<Control>;
+}
+
+/// The NAT of day 23: remembers the latest packet sent to `listen`, and sends it on to `to` whenever the network
+/// is idle. Stops the network once it sends the same `y` twice in a row.
+pub struct Nat {
+    listen: String,
+    to: String,
+    last: Option<(i64, i64)>,
+    /// The `y` of every packet sent.
+    pub sent: Vec<i64>,
+}
+
+impl Nat {
+    pub fn new(listen: &str, to: &str) -> Self {
+        Self {
+            listen: listen.to_string(),
+            to: to.to_string(),
+            last: None,
+            sent: vec![],
+        }
+    }
+}
+
+impl Hook for Nat {
+    fn idle(&mut self, channels: &mut Channels) -> Result<Control> {
+        let received = channels.take(&self.listen);
+        if let Some(packet) = received.chunks_exact(2).last() {
+            self.last = Some((packet[0], packet[1]));
+        }
+
+        let Some((x, y)) = self.last else {
+            return Ok(Control::Continue);
+        };
+        let again = self.sent.last() == Some(&y);
+        channels.send(&self.to, [x, y]);
+        self.sent.push(y);
+
+        Ok(if again { Control::Stop } else { Control::Continue })
+    }
+}
+
+#[derive(Default)]
+pub struct Network {
+    machines: Vec<Machine>,
+    pub channels: Channels,
+}
+
+impl Network {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, machine: Machine) {
+        self.machines.push(machine);
+    }
+
+    pub fn machines(&self) -> &[Machine] {
+        &self.machines
+    }
+
+    /// Runs the machines until every one has halted, or the network has gone quiet and no hook wakes it up.
+    pub fn run(&mut self, schedule: Schedule, hooks: &mut [&mut dyn Hook]) -> Result<()> {
+        let Self { machines, channels } = self;
+        let inputs: Vec<String> = machines.iter().map(|m| m.input.clone()).collect();
+
+        match schedule {
+            Schedule::RoundRobin => drive(&inputs, channels, hooks, |channels| round_robin(machines, channels)),
+            Schedule::Threads => thread::scope(|s| {
+                // one worker per machine for the whole run, a network can go through thousands of rounds
+                let workers: Vec<Worker> = machines.iter_mut().map(|m| Worker::spawn(s, m)).collect();
+                drive(&inputs, channels, hooks, |channels| threads(&workers, channels))
+            }),
+        }
+    }
+}
+
+/// What a round left behind, whether anything was read or sent but idle inputs, and which machines have halted.
+struct Round {
+    busy: bool,
+    halted: Vec<bool>,
+}
+
+/// Plays `round` over and over, `inputs` being the channels the machines read from.
+fn drive(
+    inputs: &[String],
+    channels: &mut Channels,
+    hooks: &mut [&mut dyn Hook],
+    mut round: impl FnMut(&mut Channels) -> Result<Round>,
+) -> Result<()> {
+    loop {
+        let Round { busy, halted } = round(channels)?;
+
+        if halted.iter().all(|&h| h) {
+            return Ok(());
+        }
+        if busy {
+            continue;
+        }
+
+        debug!("the network is idle");
+        for hook in hooks.iter_mut() {
+            if hook.idle(channels)? == Control::Stop {
+                return Ok(());
+            }
+        }
+        if inputs
+            .iter()
+            .zip(&halted)
+            .all(|(input, &h)| h || channels.is_empty(input))
+        {
+            return Ok(());
+        }
+    }
+}
+
+fn round_robin(machines: &mut [Machine], channels: &mut Channels) -> Result<Round> {
+    let mut busy = false;
+    for machine in machines.iter_mut() {
+        let inbox = channels.take(&machine.input);
+        let read = !inbox.is_empty();
+        let outputs = machine.turn(inbox)?;
+
+        busy |= read || !outputs.is_empty();
+        machine.route(outputs, channels)?;
+    }
+
+    Ok(Round {
+        busy,
+        halted: machines.iter().map(|m| m.halted).collect(),
+    })
+}
+
+fn threads(workers: &[Worker], channels: &mut Channels) -> Result<Round> {
+    let mut busy = false;
+    for worker in workers {
+        let inbox = channels.take(&worker.input);
+        busy |= !inbox.is_empty();
+        worker.inbox.send(inbox).expect("A machine panicked!");
+    }
+
+    let mut halted = Vec::with_capacity(workers.len());
+    for worker in workers {
+        let turn = worker.turns.recv().expect("A machine panicked!")?;
+        busy |= turn.sent;
+        halted.push(turn.halted);
+        channels.append(turn.routed);
+    }
+
+    Ok(Round { busy, halted })
+}
+
+/// A thread running the turns of a single machine, until its inbox is dropped.
+struct Worker {
+    input: String,
+    inbox: mpsc::Sender<Vec<i64>>,
+    turns: mpsc::Receiver<Result<Turn>>,
+}
+
+/// A turn of a machine on its own thread, what it sent is only delivered once the round is over.
+struct Turn {
+    sent: bool,
+    halted: bool,
+    routed: Channels,
+}
+
+impl Worker {
+    fn spawn<'scope>(s: &'scope thread::Scope<'scope, '_>, machine: &'scope mut Machine) -> Self {
+        let (inbox, inboxes) = mpsc::channel::<Vec<i64>>();
+        let (done, turns) = mpsc::channel();
+        let input = machine.input.clone();
+
+        s.spawn(move || {
+            for inbox in inboxes {
+                let turn = machine.turn(inbox).and_then(|outputs| {
+                    let mut routed = Channels::default();
+                    let sent = !outputs.is_empty();
+                    machine.route(outputs, &mut routed)?;
+                    Ok(Turn {
+                        sent,
+                        halted: machine.halted,
+                        routed,
+                    })
+                });
+                if done.send(turn).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { input, inbox, turns }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The amplifiers of day 7, `A` to `E`, the last one writing to `out` or back to the first.
+    fn amplifiers(source: &str, phases: [i64; 5], feedback: bool) -> Network {
+        let names = ["A", "B", "C", "D", "E"];
+        let mut network = Network::new();
+
+        for (i, (name, phase)) in names.iter().zip(phases).enumerate() {
+            let output = match names.get(i + 1) {
+                Some(next) => next,
+                None if feedback => "A",
+                None => "out",
+            };
+            network.add(Machine::new(Executor::new(source).unwrap(), name, output));
+            network.channels.send(name, [phase]);
+        }
+        network.channels.send("A", [0]);
+
+        network
+    }
+
+    #[test]
+    fn test_amplifiers() {
+        let source = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0";
+        let mut network = amplifiers(source, [4, 3, 2, 1, 0], false);
+        network.run(Schedule::RoundRobin, &mut []).unwrap();
+        assert_eq!(network.channels.last("out"), Some(43210));
+    }
+
+    #[test]
+    fn test_feedback() {
+        let source = "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
+
+        for schedule in [Schedule::RoundRobin, Schedule::Threads] {
+            let mut network = amplifiers(source, [9, 8, 7, 6, 5], true);
+            network.run(schedule, &mut []).unwrap();
+            assert_eq!(network.channels.last("A"), Some(139629729), "{schedule:?}");
+            assert!(network.machines().iter().all(|m| m.halted));
+        }
+    }
+
+    #[test]
+    fn test_nat() {
+        // reads its address `a`, sends `a, 10a` to 255, then keeps reading
+        let source = "3,100,104,255,4,100,1002,100,10,101,4,101,3,102,1105,1,12";
+
+        for (schedule, size) in [
+            (Schedule::RoundRobin, 3),
+            (Schedule::Threads, 3),
+            (Schedule::Threads, 50),
+        ] {
+            let mut network = Network::new();
+            for address in 0..size {
+                let machine = Machine::new(Executor::new(source).unwrap(), &address.to_string(), "")
+                    .with_output(Route::Packets(3))
+                    .with_idle_input(-1);
+                network.add(machine);
+                network.channels.send(&address.to_string(), [address]);
+            }
+
+            let mut nat = Nat::new("255", "0");
+            network.run(schedule, &mut [&mut nat]).unwrap();
+            let y = 10 * (size - 1);
+            assert_eq!(nat.sent, vec![y, y], "{schedule:?}");
+            assert!(network.machines().iter().all(|m| !m.halted));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut network = Network::new();
+        network.add(Machine::new(Executor::new("4,-1,99").unwrap(), "in", "out"));
+
+        let e = network.run(Schedule::Threads, &mut []).unwrap_err();
+        assert_eq!(e.to_string(), "The instruction at 0 failed");
+
+        let mut network = Network::new();
+        network.add(Machine::new(Executor::new("104,1,99").unwrap(), "in", "").with_output(Route::Packets(0)));
+        let e = network.run(Schedule::RoundRobin, &mut []).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "A packet of 0 values cannot hold both an address and a value!"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Procedural macros of the day crates, re-exported by `aoc-common`.
+
+use std::{collections::BTreeSet, env, fs, path::PathBuf};
+
+use proc_macro::TokenStream;
+use toml::{Table, Value};
+
+/// Generates a `#[test]` for every part of every example in the day's `examples.toml`.
+///
+/// Takes the day's `Solution`, e.g. `aoc_common::examples!(Solver);`,
+/// and names the tests `<example>_part1` and `<example>_part2`.
+/// The manifest format is described in `aoc_common::examples`.
+#[proc_macro]
+pub fn examples(input: TokenStream) -> TokenStream {
+    let solver = input.to_string();
+    let path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join("examples.toml");
+
+    let tests = fs::read_to_string(&path)
+        .map_err(|e| format!("Cannot read {}: {e}", path.display()))
+        .and_then(|source| tests(&source, &solver));
+
+    let code = match tests {
+        // including the manifest makes cargo rebuild the tests when it changes
+        Ok(tests) => format!(
+            "const _: &str = include_str!({:?});\n{tests}",
+            path.display().to_string()
+        ),
+        Err(e) => format!("compile_error!({:?});", format!("examples.toml: {e}")),
+    };
+
+    code.parse().unwrap()
+}
+
+fn tests(source: &str, solver: &str) -> Result<String, String> {
+    let manifest: Table = toml::from_str(source).map_err(|e| e.to_string())?;
+    let examples = match manifest.get("example") {
+        Some(Value::Array(examples)) => examples.as_slice(),
+        Some(_) => return Err("`example` must be an array of tables, i.e. `[[example]]`".to_string()),
+        None => &[],
+    };
+
+    let mut names = BTreeSet::new();
+    let mut res = String::new();
+
+    for example in examples {
+        let name = example
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or("every example needs a `name`")?;
+
+        let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid {
+            return Err(format!(
+                "{name:?} is not a valid test name, use lowercase letters, digits and `_`"
+            ));
+        }
+        if !names.insert(name) {
+            return Err(format!("there are two examples named {name:?}"));
+        }
+
+        let ignored: Vec<i64> = match example.get("ignore") {
+            Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_integer).collect(),
+            Some(_) => return Err(format!("the `ignore` of {name:?} must be a list of parts, e.g. `[2]`")),
+            None => vec![],
+        };
+
+        for part in 1..=2 {
+            if example.get(format!("part{part}")).is_none() {
+                continue;
+            }
+
+            let ignore = if ignored.contains(&part) { "#[ignore]\n" } else { "" };
+            res += &format!(
+                "#[test]\n{ignore}fn {name}_part{part}() {{\n    \
+                 ::aoc_common::examples::check(&{solver}, env!(\"CARGO_MANIFEST_DIR\"), {name:?}, {part});\n}}\n"
+            );
+        }
+    }
+
+    Ok(res)
+}